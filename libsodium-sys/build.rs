@@ -5,6 +5,9 @@ fn main() {
 
     if let Ok(lib_dir) = env::var("SODIUM_LIB_DIR") {
 
+        // the version of the library is only checked by pkg-config, it
+        // must be 1.0.15 or later
+
     	println!("cargo:rustc-flags=-L native={}", lib_dir);
 
         let mode = match env::var_os("SODIUM_STATIC") {
@@ -15,7 +18,8 @@ fn main() {
 
     } else {
 
-        pkg_config::find_library("libsodium").unwrap();
+        pkg_config::Config::new().atleast_version("1.0.15")
+                                 .find("libsodium").unwrap();
 
     }
 
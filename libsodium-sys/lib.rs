@@ -5,7 +5,9 @@ use libc::{c_int, c_ulonglong, c_char, size_t};
 
 include!("src/core.rs");
 
+include!("src/crypto_aead_aes256gcm.rs");
 include!("src/crypto_aead_chacha20poly1305.rs");
+include!("src/crypto_aead_xchacha20poly1305.rs");
 
 include!("src/crypto_auth.rs");
 include!("src/crypto_auth_hmacsha256.rs");
@@ -27,25 +29,34 @@ include!("src/crypto_hash.rs");
 include!("src/crypto_hash_sha256.rs");
 include!("src/crypto_hash_sha512.rs");
 
+include!("src/crypto_kdf.rs");
+include!("src/crypto_kdf_blake2b.rs");
+
+include!("src/crypto_kx.rs");
+
 include!("src/crypto_onetimeauth.rs");
 include!("src/crypto_onetimeauth_poly1305.rs");
 
+include!("src/crypto_pwhash_argon2i.rs");
+include!("src/crypto_pwhash_argon2id.rs");
 include!("src/crypto_pwhash_scryptsalsa208sha256.rs");
 
 include!("src/crypto_scalarmult.rs");
 include!("src/crypto_scalarmult_curve25519.rs");
 
+include!("src/crypto_secretbox.rs");
 include!("src/crypto_secretbox_xsalsa20poly1305.rs");
+include!("src/crypto_secretstream_xchacha20poly1305.rs");
 include!("src/crypto_shorthash_siphash24.rs");
 include!("src/crypto_sign_ed25519.rs");
 include!("src/crypto_sign_edwards25519sha512batch.rs");
 
 include!("src/crypto_stream.rs");
-include!("src/crypto_stream_aes128ctr.rs");
 include!("src/crypto_stream_chacha20.rs");
 include!("src/crypto_stream_salsa20.rs");
 include!("src/crypto_stream_salsa2012.rs");
 include!("src/crypto_stream_salsa208.rs");
+include!("src/crypto_stream_xchacha20.rs");
 include!("src/crypto_stream_xsalsa20.rs");
 
 include!("src/crypto_verify_16.rs");
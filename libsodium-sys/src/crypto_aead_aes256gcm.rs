@@ -0,0 +1,136 @@
+// crypto_aead_aes256gcm.h
+
+pub const crypto_aead_aes256gcm_KEYBYTES: usize = 32;
+pub const crypto_aead_aes256gcm_NSECBYTES: usize = 0;
+pub const crypto_aead_aes256gcm_NPUBBYTES: usize = 12;
+pub const crypto_aead_aes256gcm_ABYTES: usize = 16;
+
+#[repr(C, align(16))]
+#[derive(Copy)]
+pub struct crypto_aead_aes256gcm_state {
+    opaque: [u8; 512],
+}
+impl Clone for crypto_aead_aes256gcm_state { fn clone(&self) -> crypto_aead_aes256gcm_state { *self } }
+
+
+extern {
+    pub fn crypto_aead_aes256gcm_is_available() -> c_int;
+    pub fn crypto_aead_aes256gcm_keybytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_nsecbytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_npubbytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_abytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_statebytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_encrypt(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_encrypt_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_aes256gcm_ABYTES],
+        maclen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt_detached(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_aes256gcm_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_beforenm(
+        ctx: *mut crypto_aead_aes256gcm_state,
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_encrypt_afternm(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt_afternm(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
+    pub fn crypto_aead_aes256gcm_encrypt_detached_afternm(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_aes256gcm_ABYTES],
+        maclen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt_detached_afternm(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_aes256gcm_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
+}
+
+
+#[test]
+fn test_crypto_aead_aes256gcm_keybytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_keybytes() as usize } ==
+            crypto_aead_aes256gcm_KEYBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_nsecbytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_nsecbytes() as usize } ==
+            crypto_aead_aes256gcm_NSECBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_npubbytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_npubbytes() as usize } ==
+            crypto_aead_aes256gcm_NPUBBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_abytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_abytes() as usize } ==
+            crypto_aead_aes256gcm_ABYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_statebytes() {
+    assert!(unsafe {
+        crypto_aead_aes256gcm_statebytes() as usize
+    } == std::mem::size_of::<crypto_aead_aes256gcm_state>())
+}
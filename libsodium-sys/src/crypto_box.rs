@@ -9,6 +9,7 @@ pub const crypto_box_ZEROBYTES: usize = crypto_box_curve25519xsalsa20poly1305_ZE
 pub const crypto_box_BOXZEROBYTES: usize = crypto_box_curve25519xsalsa20poly1305_BOXZEROBYTES;
 pub const crypto_box_MACBYTES: usize = crypto_box_curve25519xsalsa20poly1305_MACBYTES;
 pub const crypto_box_PRIMITIVE: &'static str = "curve25519xsalsa20poly1305";
+pub const crypto_box_SEALBYTES: usize = crypto_box_PUBLICKEYBYTES + crypto_box_MACBYTES;
 
 
 extern {
@@ -21,6 +22,7 @@ extern {
     pub fn crypto_box_boxzerobytes() -> size_t;
     pub fn crypto_box_macbytes() -> size_t;
     pub fn crypto_box_primitive() -> *const c_char;
+    pub fn crypto_box_sealbytes() -> size_t;
 
     pub fn crypto_box_seed_keypair(
         pk: *mut [u8; crypto_box_PUBLICKEYBYTES],
@@ -100,6 +102,49 @@ extern {
         pk: *const [u8; crypto_box_PUBLICKEYBYTES],
         sk: *const [u8; crypto_box_SECRETKEYBYTES])
         -> c_int;
+    pub fn crypto_box_easy_afternm(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_NONCEBYTES],
+        k: *const [u8; crypto_box_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_open_easy_afternm(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_NONCEBYTES],
+        k: *const [u8; crypto_box_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_detached_afternm(
+        c: *mut u8,
+        mac: *mut [u8; crypto_box_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_NONCEBYTES],
+        k: *const [u8; crypto_box_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_open_detached_afternm(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_box_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_NONCEBYTES],
+        k: *const [u8; crypto_box_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_seal(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        pk: *const [u8; crypto_box_PUBLICKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_seal_open(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        pk: *const [u8; crypto_box_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_SECRETKEYBYTES])
+        -> c_int;
 }
 
 
@@ -152,6 +197,12 @@ fn test_crypto_box_macbytes() {
     } == crypto_box_MACBYTES)
 }
 #[test]
+fn test_crypto_box_sealbytes() {
+    assert!(unsafe {
+        crypto_box_sealbytes() as usize
+    } == crypto_box_SEALBYTES)
+}
+#[test]
 fn test_crypto_box_primitive() {
     unsafe {
         let s = crypto_box_primitive();
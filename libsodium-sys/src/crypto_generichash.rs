@@ -8,6 +8,9 @@ pub const crypto_generichash_KEYBYTES_MAX: usize = crypto_generichash_blake2b_KE
 pub const crypto_generichash_KEYBYTES: usize = crypto_generichash_blake2b_KEYBYTES;
 pub const crypto_generichash_PRIMITIVE: &'static str = "blake2b";
 
+#[allow(non_camel_case_types)]
+pub type crypto_generichash_state = crypto_generichash_blake2b_state;
+
 extern {
     pub fn crypto_generichash_bytes_min() -> size_t;
     pub fn crypto_generichash_bytes_max() -> size_t;
@@ -16,6 +19,7 @@ extern {
     pub fn crypto_generichash_keybytes_max() -> size_t;
     pub fn crypto_generichash_keybytes() -> size_t;
     pub fn crypto_generichash_primitive() -> *const c_char;
+    pub fn crypto_generichash_statebytes() -> size_t;
 
     pub fn crypto_generichash(
         out: *mut u8,
@@ -25,6 +29,25 @@ extern {
         key: *const u8,
         keylen: size_t)
         -> c_int;
+
+    pub fn crypto_generichash_init(
+        state: *mut crypto_generichash_state,
+        key: *const u8,
+        keylen: size_t,
+        outlen: size_t)
+        -> c_int;
+
+    pub fn crypto_generichash_update(
+        state: *mut crypto_generichash_state,
+        in_: *const u8,
+        inlen: c_ulonglong)
+        -> c_int;
+
+    pub fn crypto_generichash_final(
+        state: *mut crypto_generichash_state,
+        out: *mut u8,
+        outlen: size_t)
+        -> c_int;
 }
 
 #[test]
@@ -62,6 +85,13 @@ fn test_crypto_generichash_keybytes() {
     assert_eq!(unsafe { crypto_generichash_keybytes() as usize },
                         crypto_generichash_KEYBYTES)
 }
+
+#[test]
+fn test_crypto_generichash_statebytes() {
+    assert_eq!(unsafe { crypto_generichash_statebytes() as usize },
+                        std::mem::size_of::<crypto_generichash_state>())
+}
+
 #[test]
 fn test_crypto_generichash_primitive() {
     unsafe {
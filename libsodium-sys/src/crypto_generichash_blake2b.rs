@@ -1,5 +1,16 @@
 // crypto_generichash_blake2b.h
 
+#[repr(C, align(64))]
+#[derive(Copy, Clone)]
+pub struct crypto_generichash_blake2b_state {
+    h: [u64; 8],
+    t: [u64; 2],
+    f: [u64; 2],
+    buf: [u8; 2 * 128],
+    buflen: size_t,
+    last_node: u8,
+}
+
 pub const crypto_generichash_blake2b_BYTES_MIN: usize = 16;
 pub const crypto_generichash_blake2b_BYTES_MAX: usize = 64;
 pub const crypto_generichash_blake2b_BYTES: usize = 32;
@@ -19,6 +30,7 @@ extern {
     pub fn crypto_generichash_blake2b_keybytes() -> size_t;
     pub fn crypto_generichash_blake2b_saltbytes() -> size_t;
     pub fn crypto_generichash_blake2b_personalbytes() -> size_t;
+    pub fn crypto_generichash_blake2b_statebytes() -> size_t;
 
     pub fn crypto_generichash_blake2b(
         out: *mut u8,
@@ -39,6 +51,25 @@ extern {
         salt: *const [u8; crypto_generichash_blake2b_SALTBYTES],
         personal: *const [u8; crypto_generichash_blake2b_PERSONALBYTES])
         -> c_int;
+
+    pub fn crypto_generichash_blake2b_init(
+        state: *mut crypto_generichash_blake2b_state,
+        key: *const u8,
+        keylen: size_t,
+        outlen: size_t)
+        -> c_int;
+
+    pub fn crypto_generichash_blake2b_update(
+        state: *mut crypto_generichash_blake2b_state,
+        in_: *const u8,
+        inlen: c_ulonglong)
+        -> c_int;
+
+    pub fn crypto_generichash_blake2b_final(
+        state: *mut crypto_generichash_blake2b_state,
+        out: *mut u8,
+        outlen: size_t)
+        -> c_int;
 }
 
 #[test]
@@ -88,3 +119,9 @@ fn test_crypto_generichash_blake2b_personalbytes() {
     assert_eq!(unsafe { crypto_generichash_blake2b_personalbytes() as usize },
                         crypto_generichash_blake2b_PERSONALBYTES)
 }
+
+#[test]
+fn test_crypto_generichash_blake2b_statebytes() {
+    assert_eq!(unsafe { crypto_generichash_blake2b_statebytes() as usize },
+                        std::mem::size_of::<crypto_generichash_blake2b_state>())
+}
@@ -0,0 +1,59 @@
+// crypto_kdf.h
+
+pub const crypto_kdf_BYTES_MIN: usize = crypto_kdf_blake2b_BYTES_MIN;
+pub const crypto_kdf_BYTES_MAX: usize = crypto_kdf_blake2b_BYTES_MAX;
+pub const crypto_kdf_CONTEXTBYTES: usize = crypto_kdf_blake2b_CONTEXTBYTES;
+pub const crypto_kdf_KEYBYTES: usize = crypto_kdf_blake2b_KEYBYTES;
+pub const crypto_kdf_PRIMITIVE: &'static str = "blake2b";
+
+
+extern {
+    pub fn crypto_kdf_bytes_min() -> size_t;
+    pub fn crypto_kdf_bytes_max() -> size_t;
+    pub fn crypto_kdf_contextbytes() -> size_t;
+    pub fn crypto_kdf_keybytes() -> size_t;
+    pub fn crypto_kdf_primitive() -> *const c_char;
+
+    pub fn crypto_kdf_derive_from_key(
+        subkey: *mut u8,
+        subkey_len: size_t,
+        subkey_id: u64,
+        ctx: *const [u8; crypto_kdf_CONTEXTBYTES],
+        key: *const [u8; crypto_kdf_KEYBYTES])
+        -> c_int;
+    pub fn crypto_kdf_keygen(k: *mut [u8; crypto_kdf_KEYBYTES]);
+}
+
+
+#[test]
+fn test_crypto_kdf_bytes_min() {
+    assert!(unsafe {
+        crypto_kdf_bytes_min() as usize
+    } == crypto_kdf_BYTES_MIN)
+}
+#[test]
+fn test_crypto_kdf_bytes_max() {
+    assert!(unsafe {
+        crypto_kdf_bytes_max() as usize
+    } == crypto_kdf_BYTES_MAX)
+}
+#[test]
+fn test_crypto_kdf_contextbytes() {
+    assert!(unsafe {
+        crypto_kdf_contextbytes() as usize
+    } == crypto_kdf_CONTEXTBYTES)
+}
+#[test]
+fn test_crypto_kdf_keybytes() {
+    assert!(unsafe {
+        crypto_kdf_keybytes() as usize
+    } == crypto_kdf_KEYBYTES)
+}
+#[test]
+fn test_crypto_kdf_primitive() {
+    unsafe {
+        let s = crypto_kdf_primitive();
+        let s = std::ffi::CStr::from_ptr(s).to_bytes();
+        assert!(s == crypto_kdf_PRIMITIVE.as_bytes());
+    }
+}
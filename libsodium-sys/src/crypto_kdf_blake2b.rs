@@ -0,0 +1,48 @@
+// crypto_kdf_blake2b.h
+
+pub const crypto_kdf_blake2b_BYTES_MIN: usize = 16;
+pub const crypto_kdf_blake2b_BYTES_MAX: usize = 64;
+pub const crypto_kdf_blake2b_CONTEXTBYTES: usize = 8;
+pub const crypto_kdf_blake2b_KEYBYTES: usize = 32;
+
+
+extern {
+    pub fn crypto_kdf_blake2b_bytes_min() -> size_t;
+    pub fn crypto_kdf_blake2b_bytes_max() -> size_t;
+    pub fn crypto_kdf_blake2b_contextbytes() -> size_t;
+    pub fn crypto_kdf_blake2b_keybytes() -> size_t;
+
+    pub fn crypto_kdf_blake2b_derive_from_key(
+        subkey: *mut u8,
+        subkey_len: size_t,
+        subkey_id: u64,
+        ctx: *const [u8; crypto_kdf_blake2b_CONTEXTBYTES],
+        key: *const [u8; crypto_kdf_blake2b_KEYBYTES])
+        -> c_int;
+}
+
+
+#[test]
+fn test_crypto_kdf_blake2b_bytes_min() {
+    assert!(unsafe {
+        crypto_kdf_blake2b_bytes_min() as usize
+    } == crypto_kdf_blake2b_BYTES_MIN)
+}
+#[test]
+fn test_crypto_kdf_blake2b_bytes_max() {
+    assert!(unsafe {
+        crypto_kdf_blake2b_bytes_max() as usize
+    } == crypto_kdf_blake2b_BYTES_MAX)
+}
+#[test]
+fn test_crypto_kdf_blake2b_contextbytes() {
+    assert!(unsafe {
+        crypto_kdf_blake2b_contextbytes() as usize
+    } == crypto_kdf_blake2b_CONTEXTBYTES)
+}
+#[test]
+fn test_crypto_kdf_blake2b_keybytes() {
+    assert!(unsafe {
+        crypto_kdf_blake2b_keybytes() as usize
+    } == crypto_kdf_blake2b_KEYBYTES)
+}
@@ -1,5 +1,11 @@
 // crypto_onetimeauth_poly1305.h
 
+#[repr(C, align(16))]
+#[derive(Copy, Clone)]
+pub struct crypto_onetimeauth_poly1305_state {
+    opaque: [u8; 256],
+}
+
 pub const crypto_onetimeauth_poly1305_BYTES: usize = 16;
 pub const crypto_onetimeauth_poly1305_KEYBYTES: usize = 32;
 
@@ -15,8 +21,19 @@ extern {
         m: *const u8,
         mlen: c_ulonglong,
         k: *const [u8; crypto_onetimeauth_poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_onetimeauth_poly1305_init(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        key: *const [u8; crypto_onetimeauth_poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_onetimeauth_poly1305_update(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_onetimeauth_poly1305_final(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        a: *mut [u8; crypto_onetimeauth_poly1305_BYTES]) -> c_int;
     pub fn crypto_onetimeauth_poly1305_bytes() -> size_t;
     pub fn crypto_onetimeauth_poly1305_keybytes() -> size_t;
+    pub fn crypto_onetimeauth_poly1305_statebytes() -> size_t;
 }
 
 
@@ -30,3 +47,8 @@ fn test_crypto_onetimeauth_poly1305_keybytes() {
     assert!(unsafe { crypto_onetimeauth_poly1305_keybytes() as usize } ==
             crypto_onetimeauth_poly1305_KEYBYTES)
 }
+#[test]
+fn test_crypto_onetimeauth_poly1305_statebytes() {
+    assert!(unsafe { crypto_onetimeauth_poly1305_statebytes() as usize } ==
+            std::mem::size_of::<crypto_onetimeauth_poly1305_state>())
+}
@@ -41,6 +41,10 @@ extern {
         str_: *const [u8; crypto_pwhash_scryptsalsa208sha256_STRBYTES],
         passwd: *const u8,
         passwdlen: c_ulonglong) -> c_int;
+    pub fn crypto_pwhash_scryptsalsa208sha256_str_needs_rehash(
+        str_: *const [u8; crypto_pwhash_scryptsalsa208sha256_STRBYTES],
+        opslimit: c_ulonglong,
+        memlimit: size_t) -> c_int;
     pub fn crypto_pwhash_scryptsalsa208sha256_ll(
         passwd: *const u8,
         passwdlen: size_t,
@@ -124,6 +128,34 @@ fn test_crypto_pwhash_scryptsalsa208sha256_str() {
     assert!(ret_verify == 0);
 }
 #[test]
+fn test_crypto_pwhash_scryptsalsa208sha256_str_needs_rehash() {
+    let password = "Correct Horse Battery Staple";
+    let mut hashed_password = [0; crypto_pwhash_scryptsalsa208sha256_STRBYTES];
+    let ret_hash = unsafe {
+        crypto_pwhash_scryptsalsa208sha256_str(
+            &mut hashed_password,
+            password.as_ptr(),
+            password.len() as c_ulonglong,
+            crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_INTERACTIVE as c_ulonglong,
+            crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_INTERACTIVE as size_t)
+    };
+    assert!(ret_hash == 0);
+    let ret_same = unsafe {
+        crypto_pwhash_scryptsalsa208sha256_str_needs_rehash(
+            &hashed_password,
+            crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_INTERACTIVE as c_ulonglong,
+            crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_INTERACTIVE as size_t)
+    };
+    assert!(ret_same == 0);
+    let ret_stronger = unsafe {
+        crypto_pwhash_scryptsalsa208sha256_str_needs_rehash(
+            &hashed_password,
+            crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_SENSITIVE as c_ulonglong,
+            crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_SENSITIVE as size_t)
+    };
+    assert!(ret_stronger == 1);
+}
+#[test]
 fn test_crypto_pwhash_scryptsalsa208sha256_ll_1() {
     // See https://www.tarsnap.com/scrypt/scrypt.pdf Page 16
     let password = "";
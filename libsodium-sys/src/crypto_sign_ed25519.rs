@@ -1,5 +1,12 @@
 // crypto_sign_ed25519.h
 
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_sign_ed25519ph_state {
+    hs: crypto_hash_sha512_state,
+}
+impl Clone for crypto_sign_ed25519ph_state { fn clone(&self) -> crypto_sign_ed25519ph_state { *self } }
+
 pub const crypto_sign_ed25519_BYTES: usize = 64;
 pub const crypto_sign_ed25519_SEEDBYTES: usize = 32;
 pub const crypto_sign_ed25519_PUBLICKEYBYTES: usize = 32;
@@ -37,6 +44,34 @@ extern {
         m: *const u8,
         mlen: c_ulonglong,
         pk: *const [u8; crypto_sign_ed25519_PUBLICKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519_pk_to_curve25519(
+        curve25519_pk: *mut [u8; crypto_scalarmult_curve25519_BYTES],
+        ed25519_pk: *const [u8; crypto_sign_ed25519_PUBLICKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519_sk_to_curve25519(
+        curve25519_sk: *mut [u8; crypto_scalarmult_curve25519_BYTES],
+        ed25519_sk: *const [u8; crypto_sign_ed25519_SECRETKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519_sk_to_seed(
+        seed: *mut [u8; crypto_sign_ed25519_SEEDBYTES],
+        sk: *const [u8; crypto_sign_ed25519_SECRETKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519_sk_to_pk(
+        pk: *mut [u8; crypto_sign_ed25519_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_sign_ed25519_SECRETKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519ph_init(
+        state: *mut crypto_sign_ed25519ph_state) -> c_int;
+    pub fn crypto_sign_ed25519ph_update(
+        state: *mut crypto_sign_ed25519ph_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_sign_ed25519ph_final_create(
+        state: *mut crypto_sign_ed25519ph_state,
+        sig: *mut [u8; crypto_sign_ed25519_BYTES],
+        siglen: *mut c_ulonglong,
+        sk: *const [u8; crypto_sign_ed25519_SECRETKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519ph_final_verify(
+        state: *mut crypto_sign_ed25519ph_state,
+        sig: *const [u8; crypto_sign_ed25519_BYTES],
+        pk: *const [u8; crypto_sign_ed25519_PUBLICKEYBYTES]) -> c_int;
+    pub fn crypto_sign_ed25519ph_statebytes() -> size_t;
     pub fn crypto_sign_ed25519_bytes() -> size_t;
     pub fn crypto_sign_ed25519_seedbytes() -> size_t;
     pub fn crypto_sign_ed25519_publickeybytes() -> size_t;
@@ -68,3 +103,83 @@ fn test_crypto_sign_ed25519_secretkeybytes() {
         crypto_sign_ed25519_secretkeybytes() as usize
     } == crypto_sign_ed25519_SECRETKEYBYTES)
 }
+#[test]
+fn test_crypto_sign_ed25519ph_statebytes() {
+    assert!(unsafe {
+        crypto_sign_ed25519ph_statebytes() as usize
+    } == std::mem::size_of::<crypto_sign_ed25519ph_state>())
+}
+#[test]
+fn test_crypto_sign_ed25519ph() {
+    // corresponding to test/default/sign.c in libsodium (RFC 8032, section 7.3)
+    let seed = [0x83, 0x3f, 0xe6, 0x24, 0x09, 0x23, 0x7b, 0x9d,
+                0x62, 0xec, 0x77, 0x58, 0x75, 0x20, 0x91, 0x1e,
+                0x9a, 0x75, 0x9c, 0xec, 0x1d, 0x19, 0x75, 0x5b,
+                0x7d, 0xa9, 0x01, 0xb9, 0x6d, 0xca, 0x3d, 0x42];
+    let expected = [0x98, 0xa7, 0x02, 0x22, 0xf0, 0xb8, 0x12, 0x1a,
+                    0xa9, 0xd3, 0x0f, 0x81, 0x3d, 0x68, 0x3f, 0x80,
+                    0x9e, 0x46, 0x2b, 0x46, 0x9c, 0x7f, 0xf8, 0x76,
+                    0x39, 0x49, 0x9b, 0xb9, 0x4e, 0x6d, 0xae, 0x41,
+                    0x31, 0xf8, 0x50, 0x42, 0x46, 0x3c, 0x2a, 0x35,
+                    0x5a, 0x20, 0x03, 0xd0, 0x62, 0xad, 0xf5, 0xaa,
+                    0xa1, 0x0b, 0x8c, 0x61, 0xe6, 0x36, 0x06, 0x2a,
+                    0xaa, 0xd1, 0x1c, 0x2a, 0x26, 0x08, 0x34, 0x06];
+    let m = b"abc";
+    let mut pk = [0; crypto_sign_ed25519_PUBLICKEYBYTES];
+    let mut sk = [0; crypto_sign_ed25519_SECRETKEYBYTES];
+    let mut sig = [0; crypto_sign_ed25519_BYTES];
+    let mut siglen = 0;
+    unsafe {
+        assert!(crypto_sign_ed25519_seed_keypair(&mut pk, &mut sk, &seed) == 0);
+        let mut st: crypto_sign_ed25519ph_state = std::mem::zeroed();
+        assert!(crypto_sign_ed25519ph_init(&mut st) == 0);
+        assert!(crypto_sign_ed25519ph_update(&mut st, m.as_ptr(), m.len() as c_ulonglong) == 0);
+        assert!(crypto_sign_ed25519ph_final_create(&mut st, &mut sig, &mut siglen, &sk) == 0);
+        assert!(siglen == crypto_sign_ed25519_BYTES as c_ulonglong);
+        assert!(sig[..] == expected[..]);
+        let mut st: crypto_sign_ed25519ph_state = std::mem::zeroed();
+        assert!(crypto_sign_ed25519ph_init(&mut st) == 0);
+        assert!(crypto_sign_ed25519ph_update(&mut st, m.as_ptr(), m.len() as c_ulonglong) == 0);
+        assert!(crypto_sign_ed25519ph_final_verify(&mut st, &sig, &pk) == 0);
+    }
+}
+#[test]
+fn test_crypto_sign_ed25519_to_curve25519() {
+    // corresponding to test/default/ed25519_convert.c in libsodium
+    let seed = [0x42, 0x11, 0x51, 0xa4, 0x59, 0xfa, 0xea, 0xde,
+                0x3d, 0x24, 0x71, 0x15, 0xf9, 0x4a, 0xed, 0xae,
+                0x42, 0x31, 0x81, 0x24, 0x09, 0x5a, 0xfa, 0xbe,
+                0x4d, 0x14, 0x51, 0xa5, 0x59, 0xfa, 0xed, 0xee];
+    let mut pk = [0; crypto_sign_ed25519_PUBLICKEYBYTES];
+    let mut sk = [0; crypto_sign_ed25519_SECRETKEYBYTES];
+    let mut curve25519_pk = [0; crypto_scalarmult_curve25519_BYTES];
+    let mut curve25519_sk = [0; crypto_scalarmult_curve25519_BYTES];
+    unsafe {
+        assert!(crypto_sign_ed25519_seed_keypair(&mut pk, &mut sk, &seed) == 0);
+        assert!(crypto_sign_ed25519_pk_to_curve25519(&mut curve25519_pk, &pk) == 0);
+        assert!(crypto_sign_ed25519_sk_to_curve25519(&mut curve25519_sk, &sk) == 0);
+    }
+    assert!(curve25519_pk == [0xf1, 0x81, 0x4f, 0x0e, 0x8f, 0xf1, 0x04, 0x3d,
+                              0x8a, 0x44, 0xd2, 0x5b, 0xab, 0xff, 0x3c, 0xed,
+                              0xca, 0xe6, 0xc2, 0x2c, 0x3e, 0xda, 0xa4, 0x8f,
+                              0x85, 0x7a, 0xe7, 0x0d, 0xe2, 0xba, 0xae, 0x50]);
+    assert!(curve25519_sk == [0x80, 0x52, 0x03, 0x03, 0x76, 0xd4, 0x71, 0x12,
+                              0xbe, 0x7f, 0x73, 0xed, 0x7a, 0x01, 0x92, 0x93,
+                              0xdd, 0x12, 0xad, 0x91, 0x0b, 0x65, 0x44, 0x55,
+                              0x79, 0x8b, 0x46, 0x67, 0xd7, 0x3d, 0xe1, 0x66]);
+}
+#[test]
+fn test_crypto_sign_ed25519_sk_to_seed_pk() {
+    let seed = [0x42; crypto_sign_ed25519_SEEDBYTES];
+    let mut pk = [0; crypto_sign_ed25519_PUBLICKEYBYTES];
+    let mut sk = [0; crypto_sign_ed25519_SECRETKEYBYTES];
+    let mut seed2 = [0; crypto_sign_ed25519_SEEDBYTES];
+    let mut pk2 = [0; crypto_sign_ed25519_PUBLICKEYBYTES];
+    unsafe {
+        assert!(crypto_sign_ed25519_seed_keypair(&mut pk, &mut sk, &seed) == 0);
+        assert!(crypto_sign_ed25519_sk_to_seed(&mut seed2, &sk) == 0);
+        assert!(crypto_sign_ed25519_sk_to_pk(&mut pk2, &sk) == 0);
+    }
+    assert!(seed == seed2);
+    assert!(pk == pk2);
+}
@@ -0,0 +1,523 @@
+macro_rules! aead_module (($seal_name:ident,
+                           $open_name:ident,
+                           $seal_detached_name:ident,
+                           $open_detached_name:ident,
+                           $cipher:ident,
+                           $keybytes:expr,
+                           $noncebytes:expr,
+                           $tagbytes:expr) => (
+
+use libc::c_ulonglong;
+use std::iter::repeat;
+use std::ptr;
+use randombytes::randombytes_into;
+use rustc_serialize;
+use crypto::aead::Aead;
+
+pub const KEYBYTES: usize = $keybytes;
+pub const NONCEBYTES: usize = $noncebytes;
+pub const TAGBYTES: usize = $tagbytes;
+
+/// `Key` for authenticated encryption with additional data
+///
+/// When a `Key` goes out of scope its contents
+/// will be zeroed out
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/// `Nonce` for authenticated encryption with additional data
+#[derive(Copy)]
+pub struct Nonce(pub [u8; NONCEBYTES]);
+
+newtype_clone!(Nonce);
+newtype_impl!(Nonce, NONCEBYTES);
+
+/// Authentication `Tag` for the detached form of authenticated encryption
+/// with additional data
+#[derive(Copy)]
+pub struct Tag(pub [u8; TAGBYTES]);
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, TAGBYTES);
+
+/// `gen_key()` randomly generates a secret key
+///
+/// THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn gen_key() -> Key {
+    let mut key = [0; KEYBYTES];
+    randombytes_into(&mut key);
+    Key(key)
+}
+
+/// `gen_nonce()` randomly generates a nonce
+///
+/// THREAD SAFETY: `gen_nonce()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+///
+/// NOTE: When using primitives with short nonces (e.g. chacha20poly1305)
+/// do not use random nonces since the probability of nonce-collision is not negligible
+pub fn gen_nonce() -> Nonce {
+    let mut nonce = [0; NONCEBYTES];
+    randombytes_into(&mut nonce);
+    Nonce(nonce)
+}
+
+/// `seal()` encrypts and authenticates a message `m` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`. It returns a ciphertext `c`.
+pub fn seal(m: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Vec<u8> {
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + TAGBYTES).collect();
+    let mut clen: c_ulonglong = 0;
+    unsafe {
+        $seal_name(c.as_mut_ptr(),
+                   &mut clen,
+                   m.as_ptr(),
+                   m.len() as c_ulonglong,
+                   ad_p,
+                   ad_len as c_ulonglong,
+                   ptr::null(),
+                   n,
+                   k);
+    }
+    c.truncate(clen as usize);
+    c
+}
+
+/// `open()` verifies and decrypts a ciphertext `c` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open()` returns `None`.
+pub fn open(c: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Option<Vec<u8>> {
+    if c.len() < TAGBYTES {
+        return None;
+    }
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - TAGBYTES).collect();
+    let mut mlen: c_ulonglong = 0;
+    let ret = unsafe {
+        $open_name(m.as_mut_ptr(),
+                   &mut mlen,
+                   ptr::null_mut(),
+                   c.as_ptr(),
+                   c.len() as c_ulonglong,
+                   ad_p,
+                   ad_len as c_ulonglong,
+                   n,
+                   k)
+    };
+    if ret == 0 {
+        m.truncate(mlen as usize);
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_inplace()` encrypts and authenticates the message held in `buf` together
+/// with optional plaintext data `ad` using a secret key `k` and a nonce `n`. On
+/// return `buf` holds the ciphertext, laid out the same way as the output of `seal()`.
+///
+/// `buf` is only reallocated if its capacity is less than `TAGBYTES` more than
+/// its length.
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    ad: Option<&[u8]>,
+                    &Nonce(ref n): &Nonce,
+                    &Key(ref k): &Key) {
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mlen = buf.len();
+    let mut tag = [0u8; TAGBYTES];
+    let mut taglen: c_ulonglong = 0;
+    unsafe {
+        let p = buf.as_mut_ptr();
+        $seal_detached_name(p,
+                            &mut tag,
+                            &mut taglen,
+                            p,
+                            mlen as c_ulonglong,
+                            ad_p,
+                            ad_len as c_ulonglong,
+                            ptr::null(),
+                            n,
+                            k);
+    }
+    buf.extend(tag.iter().cloned());
+}
+
+/// `open_inplace()` verifies and decrypts a ciphertext `c`, as produced by `seal()`,
+/// together with optional plaintext data `ad` using a secret key `k` and a nonce `n`.
+/// The message is decrypted in place and `Ok(m)` is returned, where `m` is the
+/// plaintext part of `c`.
+/// If the ciphertext fails verification, `open_inplace()` returns `Err(())` and
+/// the plaintext part of `c` is overwritten with zeros.
+pub fn open_inplace<'a>(c: &'a mut [u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &Key(ref k): &Key) -> Result<&'a [u8], ()> {
+    if c.len() < TAGBYTES {
+        return Err(());
+    }
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mlen = c.len() - TAGBYTES;
+    let ret = unsafe {
+        let p = c.as_mut_ptr();
+        let ptag = p.offset(mlen as isize);
+        $open_detached_name(p,
+                            ptr::null_mut(),
+                            p,
+                            mlen as c_ulonglong,
+                            ptag as *const [u8; TAGBYTES],
+                            ad_p,
+                            ad_len as c_ulonglong,
+                            n,
+                            k)
+    };
+    if ret == 0 {
+        Ok(&c[..mlen])
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_detached()` encrypts and authenticates a message `m` together with
+/// optional plaintext data `ad` using a secret key `k` and a nonce `n`.
+/// It returns the encrypted message `c`, which has the same length as `m`,
+/// and the authentication tag separately.
+pub fn seal_detached(m: &[u8],
+                     ad: Option<&[u8]>,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> (Vec<u8>, Tag) {
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; TAGBYTES];
+    let mut taglen: c_ulonglong = 0;
+    unsafe {
+        $seal_detached_name(c.as_mut_ptr(),
+                            &mut tag,
+                            &mut taglen,
+                            m.as_ptr(),
+                            m.len() as c_ulonglong,
+                            ad_p,
+                            ad_len as c_ulonglong,
+                            ptr::null(),
+                            n,
+                            k);
+    }
+    (c, Tag(tag))
+}
+
+/// `open_detached()` verifies and decrypts an encrypted message `c` and its
+/// authentication tag `tag` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_detached()` returns `None`.
+pub fn open_detached(c: &[u8],
+                     ad: Option<&[u8]>,
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Option<Vec<u8>> {
+    let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                           .unwrap_or((ptr::null(), 0));
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        $open_detached_name(m.as_mut_ptr(),
+                            ptr::null_mut(),
+                            c.as_ptr(),
+                            c.len() as c_ulonglong,
+                            tag,
+                            ad_p,
+                            ad_len as c_ulonglong,
+                            n,
+                            k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// Marker type implementing the `Aead` trait for this construction
+#[derive(Clone, Copy)]
+pub struct $cipher;
+
+impl Aead for $cipher {
+    type Key = Key;
+    type Nonce = Nonce;
+    type Tag = Tag;
+
+    const KEYBYTES: usize = KEYBYTES;
+    const NONCEBYTES: usize = NONCEBYTES;
+    const TAGBYTES: usize = TAGBYTES;
+
+    fn key_from_slice(bs: &[u8]) -> Option<Key> {
+        Key::from_slice(bs)
+    }
+
+    fn nonce_from_slice(bs: &[u8]) -> Option<Nonce> {
+        Nonce::from_slice(bs)
+    }
+
+    fn seal(m: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Result<Vec<u8>, ()> {
+        Ok(seal(m, ad, n, k))
+    }
+
+    fn open(c: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Option<Vec<u8>> {
+        open(c, ad, n, k)
+    }
+
+    fn seal_in_place(buf: &mut Vec<u8>,
+                     ad: Option<&[u8]>,
+                     n: &Nonce,
+                     k: &Key) -> Result<(), ()> {
+        seal_inplace(buf, ad, n, k);
+        Ok(())
+    }
+
+    fn open_in_place<'a>(c: &'a mut [u8],
+                         ad: Option<&[u8]>,
+                         n: &Nonce,
+                         k: &Key) -> Result<&'a [u8], ()> {
+        open_inplace(c, ad, n, k)
+    }
+}
+
+#[cfg(test)]
+mod test_m {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_seal_open() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k);
+            let m2 = open(&c, Some(&ad), &n, &k).unwrap();
+            assert!(m == m2);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_no_ad() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let m = randombytes(i);
+            let c = seal(&m, None, &n, &k);
+            let m2 = open(&c, None, &n, &k).unwrap();
+            assert!(m == m2);
+            assert!(open(&c, Some(&[]), &n, &k).unwrap() == m);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let mut ad = randombytes(i);
+            let m = randombytes(i);
+            let mut c = seal(&m, Some(&ad), &n, &k);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open(&c, Some(&ad), &n, &k));
+                c[j] ^= 0x20;
+            }
+            for j in (0..ad.len()) {
+                ad[j] ^= 0x20;
+                assert!(None == open(&c, Some(&ad), &n, &k));
+                ad[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seal_open_inplace() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let mut buf = m.clone();
+            seal_inplace(&mut buf, Some(&ad), &n, &k);
+            assert!(buf == seal(&m, Some(&ad), &n, &k));
+            let opened = open_inplace(&mut buf, Some(&ad), &n, &k);
+            assert!(Ok(&m[..]) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_inplace_no_realloc() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut buf = Vec::with_capacity(64 + TAGBYTES);
+        buf.extend(b"some data".iter().cloned());
+        let p = buf.as_ptr();
+        seal_inplace(&mut buf, None, &n, &k);
+        assert!(p == buf.as_ptr());
+    }
+
+    #[test]
+    fn test_open_inplace_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k);
+            for j in (0..c.len()) {
+                let mut c2 = c.clone();
+                c2[j] ^= 0x20;
+                assert!(Err(()) == open_inplace(&mut c2, Some(&ad), &n, &k));
+            }
+            let mut c2 = c.clone();
+            assert!(Err(()) == open_inplace(&mut c2, Some(&[0x20]), &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let (c, tag) = seal_detached(&m, Some(&ad), &n, &k);
+            assert!(c.len() == m.len());
+            let m2 = open_detached(&c, Some(&ad), &tag, &n, &k).unwrap();
+            assert!(m == m2);
+        }
+    }
+
+    #[test]
+    fn test_seal_detached_same_as_seal() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k);
+            let (cd, Tag(tag)) = seal_detached(&m, Some(&ad), &n, &k);
+            assert!(&c[..m.len()] == &cd[..]);
+            assert!(&c[m.len()..] == &tag[..]);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let mut ad = randombytes(i);
+            let m = randombytes(i);
+            let (mut c, Tag(mut tag)) = seal_detached(&m, Some(&ad), &n, &k);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open_detached(&c, Some(&ad), &Tag(tag), &n, &k));
+                c[j] ^= 0x20;
+            }
+            for j in (0..tag.len()) {
+                tag[j] ^= 0x20;
+                assert!(None == open_detached(&c, Some(&ad), &Tag(tag), &n, &k));
+                tag[j] ^= 0x20;
+            }
+            for j in (0..ad.len()) {
+                ad[j] ^= 0x20;
+                assert!(None == open_detached(&c, Some(&ad), &Tag(tag), &n, &k));
+                ad[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_short() {
+        let k = gen_key();
+        let n = gen_nonce();
+        for i in (0..TAGBYTES) {
+            let mut c = [0; TAGBYTES];
+            assert!(None == open(&c[..i], None, &n, &k));
+            assert!(Err(()) == open_inplace(&mut c[..i], None, &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_serialisation() {
+        for _ in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let (_, tag) = seal_detached(&[], None, &n, &k);
+            round_trip(k);
+            round_trip(n);
+            round_trip(tag);
+        }
+    }
+}
+
+#[cfg(feature = "benchmarks")]
+#[cfg(test)]
+mod bench_m {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(&m, None, &n, &k), None, &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                let (c, tag) = seal_detached(&m, None, &n, &k);
+                open_detached(&c, None, &tag, &n, &k).unwrap();
+            }
+        });
+    }
+}
+
+));
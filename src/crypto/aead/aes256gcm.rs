@@ -0,0 +1,732 @@
+//! AES-256 in Galois/Counter Mode as specified in
+//! [NIST SP 800-38D](http://csrc.nist.gov/publications/nistpubs/800-38D/SP-800-38D.pdf),
+//! with a 96-bit nonce.
+//!
+//! This implementation requires hardware support for the AES-NI and CLMUL
+//! instructions. Use `is_available()` to check whether the current CPU
+//! provides them: when it does not, `seal()` and `precompute()` return
+//! `Err(())` and `open()` returns `None`. `is_available()` relies on the CPU
+//! features detected by `sodiumoxide::init()`, which has to be called first.
+//!
+//! No more than ~350 GB of input data should be encrypted with a given key,
+//! and the nonces are too short to be safely generated at random for a
+//! large number of messages. Unless AES-GCM is required for interoperability,
+//! prefer `xchacha20poly1305_ietf`.
+use ffi;
+use libc::{c_ulonglong, size_t};
+use randombytes::randombytes_into;
+use rustc_serialize;
+use crypto::aead::Aead;
+use std::iter::repeat;
+use std::{mem, ptr};
+
+pub const KEYBYTES: usize = ffi::crypto_aead_aes256gcm_KEYBYTES;
+pub const NONCEBYTES: usize = ffi::crypto_aead_aes256gcm_NPUBBYTES;
+pub const TAGBYTES: usize = ffi::crypto_aead_aes256gcm_ABYTES;
+
+/// `Key` for authenticated encryption with additional data
+///
+/// When a `Key` goes out of scope its contents
+/// will be zeroed out
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/// `Nonce` for authenticated encryption with additional data
+#[derive(Copy)]
+pub struct Nonce(pub [u8; NONCEBYTES]);
+
+newtype_clone!(Nonce);
+newtype_impl!(Nonce, NONCEBYTES);
+
+/// Authentication `Tag` for the detached form of authenticated encryption
+/// with additional data
+#[derive(Copy)]
+pub struct Tag(pub [u8; TAGBYTES]);
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, TAGBYTES);
+
+/// `is_available()` returns `true` if the current CPU supports the
+/// instructions required by AES256-GCM.
+///
+/// `sodiumoxide::init()` must have been called before, otherwise
+/// `is_available()` returns `false`.
+pub fn is_available() -> bool {
+    unsafe { ffi::crypto_aead_aes256gcm_is_available() == 1 }
+}
+
+/// `gen_key()` randomly generates a secret key
+///
+/// THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn gen_key() -> Key {
+    let mut key = [0; KEYBYTES];
+    randombytes_into(&mut key);
+    Key(key)
+}
+
+/// `gen_nonce()` randomly generates a nonce
+///
+/// THREAD SAFETY: `gen_nonce()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+///
+/// NOTE: The nonces used by AES256-GCM are only 96 bits long, so do not use
+/// random nonces for a large number of messages with the same key.
+pub fn gen_nonce() -> Nonce {
+    let mut nonce = [0; NONCEBYTES];
+    randombytes_into(&mut nonce);
+    Nonce(nonce)
+}
+
+fn ad_parts(ad: Option<&[u8]>) -> (*const u8, c_ulonglong) {
+    ad.map(|ad| (ad.as_ptr(), ad.len() as c_ulonglong))
+      .unwrap_or((ptr::null(), 0))
+}
+
+/// `seal()` encrypts and authenticates a message `m` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`. It returns a ciphertext `Ok(c)`.
+/// If AES256-GCM is not available on the current CPU, `seal()` returns `Err(())`.
+pub fn seal(m: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Result<Vec<u8>, ()> {
+    if !is_available() {
+        return Err(());
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + TAGBYTES).collect();
+    let mut clen: c_ulonglong = 0;
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_encrypt(c.as_mut_ptr(),
+                                           &mut clen,
+                                           m.as_ptr(),
+                                           m.len() as c_ulonglong,
+                                           ad_p,
+                                           ad_len,
+                                           ptr::null(),
+                                           n,
+                                           k)
+    };
+    if ret == 0 {
+        c.truncate(clen as usize);
+        Ok(c)
+    } else {
+        Err(())
+    }
+}
+
+/// `open()` verifies and decrypts a ciphertext `c` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, or if AES256-GCM is not available on the
+/// current CPU, `open()` returns `None`.
+pub fn open(c: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Option<Vec<u8>> {
+    if !is_available() || c.len() < TAGBYTES {
+        return None;
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - TAGBYTES).collect();
+    let mut mlen: c_ulonglong = 0;
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_decrypt(m.as_mut_ptr(),
+                                           &mut mlen,
+                                           ptr::null_mut(),
+                                           c.as_ptr(),
+                                           c.len() as c_ulonglong,
+                                           ad_p,
+                                           ad_len,
+                                           n,
+                                           k)
+    };
+    if ret == 0 {
+        m.truncate(mlen as usize);
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_inplace()` encrypts and authenticates the message held in `buf` together
+/// with optional plaintext data `ad` using a secret key `k` and a nonce `n`. On
+/// return `buf` holds the ciphertext, laid out the same way as the output of `seal()`.
+/// If AES256-GCM is not available on the current CPU, `seal_inplace()` returns
+/// `Err(())` and leaves `buf` untouched.
+///
+/// `buf` is only reallocated if its capacity is less than `TAGBYTES` more than
+/// its length.
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    ad: Option<&[u8]>,
+                    &Nonce(ref n): &Nonce,
+                    &Key(ref k): &Key) -> Result<(), ()> {
+    if !is_available() {
+        return Err(());
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mlen = buf.len();
+    let mut tag = [0u8; TAGBYTES];
+    let mut taglen: c_ulonglong = 0;
+    let ret = unsafe {
+        let p = buf.as_mut_ptr();
+        ffi::crypto_aead_aes256gcm_encrypt_detached(p,
+                                                    &mut tag,
+                                                    &mut taglen,
+                                                    p,
+                                                    mlen as c_ulonglong,
+                                                    ad_p,
+                                                    ad_len,
+                                                    ptr::null(),
+                                                    n,
+                                                    k)
+    };
+    if ret == 0 {
+        buf.extend(tag.iter().cloned());
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// `open_inplace()` verifies and decrypts a ciphertext `c`, as produced by `seal()`,
+/// together with optional plaintext data `ad` using a secret key `k` and a nonce `n`.
+/// The message is decrypted in place and `Ok(m)` is returned, where `m` is the
+/// plaintext part of `c`.
+/// If the ciphertext fails verification, `open_inplace()` returns `Err(())` and
+/// the plaintext part of `c` is overwritten with zeros. If AES256-GCM is not
+/// available on the current CPU, `open_inplace()` returns `Err(())` and leaves `c`
+/// untouched.
+pub fn open_inplace<'a>(c: &'a mut [u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &Key(ref k): &Key) -> Result<&'a [u8], ()> {
+    if !is_available() || c.len() < TAGBYTES {
+        return Err(());
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mlen = c.len() - TAGBYTES;
+    let ret = unsafe {
+        let p = c.as_mut_ptr();
+        let ptag = p.offset(mlen as isize);
+        ffi::crypto_aead_aes256gcm_decrypt_detached(p,
+                                                    ptr::null_mut(),
+                                                    p,
+                                                    mlen as c_ulonglong,
+                                                    ptag as *const [u8; TAGBYTES],
+                                                    ad_p,
+                                                    ad_len,
+                                                    n,
+                                                    k)
+    };
+    if ret == 0 {
+        Ok(&c[..mlen])
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_detached()` encrypts and authenticates a message `m` together with
+/// optional plaintext data `ad` using a secret key `k` and a nonce `n`.
+/// It returns the encrypted message `c`, which has the same length as `m`,
+/// and the authentication tag separately.
+/// If AES256-GCM is not available on the current CPU, `seal_detached()` returns `Err(())`.
+pub fn seal_detached(m: &[u8],
+                     ad: Option<&[u8]>,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Result<(Vec<u8>, Tag), ()> {
+    if !is_available() {
+        return Err(());
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; TAGBYTES];
+    let mut taglen: c_ulonglong = 0;
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_encrypt_detached(c.as_mut_ptr(),
+                                                    &mut tag,
+                                                    &mut taglen,
+                                                    m.as_ptr(),
+                                                    m.len() as c_ulonglong,
+                                                    ad_p,
+                                                    ad_len,
+                                                    ptr::null(),
+                                                    n,
+                                                    k)
+    };
+    if ret == 0 {
+        Ok((c, Tag(tag)))
+    } else {
+        Err(())
+    }
+}
+
+/// `open_detached()` verifies and decrypts an encrypted message `c` and its
+/// authentication tag `tag` together with optional plaintext data `ad`
+/// using a secret key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, or if AES256-GCM is not available on the
+/// current CPU, `open_detached()` returns `None`.
+pub fn open_detached(c: &[u8],
+                     ad: Option<&[u8]>,
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Option<Vec<u8>> {
+    if !is_available() {
+        return None;
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_decrypt_detached(m.as_mut_ptr(),
+                                                    ptr::null_mut(),
+                                                    c.as_ptr(),
+                                                    c.len() as c_ulonglong,
+                                                    tag,
+                                                    ad_p,
+                                                    ad_len,
+                                                    n,
+                                                    k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// Applications that encrypt or decrypt several messages with the same key can
+/// gain speed by expanding the key once with `precompute()` and then using
+/// `seal_precomputed()` and `open_precomputed()`.
+///
+/// A `PrecomputedKey` can only be obtained when AES256-GCM is available on the
+/// current CPU, so the functions taking one never fail for that reason.
+///
+/// When a `PrecomputedKey` goes out of scope its contents will be zeroed out
+#[derive(Clone)]
+pub struct PrecomputedKey(ffi::crypto_aead_aes256gcm_state);
+
+impl Drop for PrecomputedKey {
+    fn drop(&mut self) {
+        let &mut PrecomputedKey(ref mut state) = self;
+        unsafe {
+            ffi::sodium_memzero(state as *mut ffi::crypto_aead_aes256gcm_state as *mut u8,
+                                mem::size_of::<ffi::crypto_aead_aes256gcm_state>() as size_t);
+        }
+    }
+}
+
+/// `precompute()` expands the key `k` into a `PrecomputedKey` that can be used by
+/// `seal_precomputed()` and `open_precomputed()`.
+/// If AES256-GCM is not available on the current CPU, `precompute()` returns `Err(())`.
+pub fn precompute(&Key(ref k): &Key) -> Result<PrecomputedKey, ()> {
+    if !is_available() {
+        return Err(());
+    }
+    let mut state: ffi::crypto_aead_aes256gcm_state = unsafe { mem::zeroed() };
+    let ret = unsafe { ffi::crypto_aead_aes256gcm_beforenm(&mut state, k) };
+    if ret == 0 {
+        Ok(PrecomputedKey(state))
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_precomputed()` encrypts and authenticates a message `m` together with optional
+/// plaintext data `ad` using a precomputed key `k` and a nonce `n`. It returns a
+/// ciphertext `c`.
+pub fn seal_precomputed(m: &[u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Vec<u8> {
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + TAGBYTES).collect();
+    let mut clen: c_ulonglong = 0;
+    unsafe {
+        ffi::crypto_aead_aes256gcm_encrypt_afternm(c.as_mut_ptr(),
+                                                   &mut clen,
+                                                   m.as_ptr(),
+                                                   m.len() as c_ulonglong,
+                                                   ad_p,
+                                                   ad_len,
+                                                   ptr::null(),
+                                                   n,
+                                                   k);
+    }
+    c.truncate(clen as usize);
+    c
+}
+
+/// `open_precomputed()` verifies and decrypts a ciphertext `c` together with optional
+/// plaintext data `ad` using a precomputed key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_precomputed()` returns `None`.
+pub fn open_precomputed(c: &[u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
+    if c.len() < TAGBYTES {
+        return None;
+    }
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - TAGBYTES).collect();
+    let mut mlen: c_ulonglong = 0;
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_decrypt_afternm(m.as_mut_ptr(),
+                                                   &mut mlen,
+                                                   ptr::null_mut(),
+                                                   c.as_ptr(),
+                                                   c.len() as c_ulonglong,
+                                                   ad_p,
+                                                   ad_len,
+                                                   n,
+                                                   k)
+    };
+    if ret == 0 {
+        m.truncate(mlen as usize);
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_detached_precomputed()` encrypts and authenticates a message `m` together
+/// with optional plaintext data `ad` using a precomputed key `k` and a nonce `n`.
+/// It returns the encrypted message `c` and the authentication tag separately.
+pub fn seal_detached_precomputed(m: &[u8],
+                                 ad: Option<&[u8]>,
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> (Vec<u8>, Tag) {
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; TAGBYTES];
+    let mut taglen: c_ulonglong = 0;
+    unsafe {
+        ffi::crypto_aead_aes256gcm_encrypt_detached_afternm(c.as_mut_ptr(),
+                                                            &mut tag,
+                                                            &mut taglen,
+                                                            m.as_ptr(),
+                                                            m.len() as c_ulonglong,
+                                                            ad_p,
+                                                            ad_len,
+                                                            ptr::null(),
+                                                            n,
+                                                            k);
+    }
+    (c, Tag(tag))
+}
+
+/// `open_detached_precomputed()` verifies and decrypts an encrypted message `c` and
+/// its authentication tag `tag` together with optional plaintext data `ad` using a
+/// precomputed key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_detached_precomputed()` returns `None`.
+pub fn open_detached_precomputed(c: &[u8],
+                                 ad: Option<&[u8]>,
+                                 &Tag(ref tag): &Tag,
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
+    let (ad_p, ad_len) = ad_parts(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_decrypt_detached_afternm(m.as_mut_ptr(),
+                                                            ptr::null_mut(),
+                                                            c.as_ptr(),
+                                                            c.len() as c_ulonglong,
+                                                            tag,
+                                                            ad_p,
+                                                            ad_len,
+                                                            n,
+                                                            k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// Marker type implementing the `Aead` trait for AES256-GCM
+#[derive(Clone, Copy)]
+pub struct Aes256Gcm;
+
+impl Aead for Aes256Gcm {
+    type Key = Key;
+    type Nonce = Nonce;
+    type Tag = Tag;
+
+    const KEYBYTES: usize = KEYBYTES;
+    const NONCEBYTES: usize = NONCEBYTES;
+    const TAGBYTES: usize = TAGBYTES;
+
+    fn key_from_slice(bs: &[u8]) -> Option<Key> {
+        Key::from_slice(bs)
+    }
+
+    fn nonce_from_slice(bs: &[u8]) -> Option<Nonce> {
+        Nonce::from_slice(bs)
+    }
+
+    fn seal(m: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Result<Vec<u8>, ()> {
+        seal(m, ad, n, k)
+    }
+
+    fn open(c: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Option<Vec<u8>> {
+        open(c, ad, n, k)
+    }
+
+    fn seal_in_place(buf: &mut Vec<u8>,
+                     ad: Option<&[u8]>,
+                     n: &Nonce,
+                     k: &Key) -> Result<(), ()> {
+        seal_inplace(buf, ad, n, k)
+    }
+
+    fn open_in_place<'a>(c: &'a mut [u8],
+                         ad: Option<&[u8]>,
+                         n: &Nonce,
+                         k: &Key) -> Result<&'a [u8], ()> {
+        open_inplace(c, ad, n, k)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_unavailable() {
+        ::init();
+        if is_available() {
+            return;
+        }
+        let k = gen_key();
+        let n = gen_nonce();
+        assert!(seal(b"", None, &n, &k) == Err(()));
+        assert!(seal_detached(b"", None, &n, &k).is_err());
+        assert!(open(&[0; TAGBYTES], None, &n, &k) == None);
+        assert!(open_detached(b"", None, &Tag([0; TAGBYTES]), &n, &k) == None);
+        assert!(precompute(&k).is_err());
+        let mut buf = b"some data".to_vec();
+        assert!(seal_inplace(&mut buf, None, &n, &k) == Err(()));
+        assert!(&buf[..] == b"some data");
+        assert!(open_inplace(&mut [0; TAGBYTES], None, &n, &k) == Err(()));
+    }
+
+    #[test]
+    fn test_seal_open() {
+        use randombytes::randombytes;
+        ::init();
+        if !is_available() {
+            return;
+        }
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k).unwrap();
+            let m2 = open(&c, Some(&ad), &n, &k).unwrap();
+            assert!(m == m2);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_tamper() {
+        use randombytes::randombytes;
+        ::init();
+        if !is_available() {
+            return;
+        }
+        for i in (0..32usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let mut ad = randombytes(i);
+            let m = randombytes(i);
+            let mut c = seal(&m, Some(&ad), &n, &k).unwrap();
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open(&c, Some(&ad), &n, &k));
+                c[j] ^= 0x20;
+            }
+            for j in (0..ad.len()) {
+                ad[j] ^= 0x20;
+                assert!(None == open(&c, Some(&ad), &n, &k));
+                ad[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seal_open_inplace() {
+        use randombytes::randombytes;
+        ::init();
+        if !is_available() {
+            return;
+        }
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let mut buf = m.clone();
+            seal_inplace(&mut buf, Some(&ad), &n, &k).unwrap();
+            assert!(buf == seal(&m, Some(&ad), &n, &k).unwrap());
+            let opened = open_inplace(&mut buf, Some(&ad), &n, &k);
+            assert!(Ok(&m[..]) == opened);
+            buf[0] ^= 0x20;
+            assert!(Err(()) == open_inplace(&mut buf, Some(&ad), &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached() {
+        use randombytes::randombytes;
+        ::init();
+        if !is_available() {
+            return;
+        }
+        for i in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k).unwrap();
+            let (cd, Tag(tag)) = seal_detached(&m, Some(&ad), &n, &k).unwrap();
+            assert!(&c[..m.len()] == &cd[..]);
+            assert!(&c[m.len()..] == &tag[..]);
+            let m2 = open_detached(&cd, Some(&ad), &Tag(tag), &n, &k).unwrap();
+            assert!(m == m2);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_precomputed() {
+        use randombytes::randombytes;
+        ::init();
+        if !is_available() {
+            return;
+        }
+        for i in (0..256usize) {
+            let k = gen_key();
+            let pk = precompute(&k).unwrap();
+            let n = gen_nonce();
+            let ad = randombytes(i);
+            let m = randombytes(i);
+            let c = seal(&m, Some(&ad), &n, &k).unwrap();
+            let c2 = seal_precomputed(&m, Some(&ad), &n, &pk);
+            assert!(c == c2);
+            let m2 = open_precomputed(&c, Some(&ad), &n, &pk.clone()).unwrap();
+            assert!(m == m2);
+            let (cd, tag) = seal_detached_precomputed(&m, Some(&ad), &n, &pk);
+            assert!(&c[..m.len()] == &cd[..]);
+            let m3 = open_detached_precomputed(&cd, Some(&ad), &tag, &n, &pk).unwrap();
+            assert!(m == m3);
+            if !c.is_empty() {
+                let mut c = c;
+                c[0] ^= 0x20;
+                assert!(None == open_precomputed(&c, Some(&ad), &n, &pk));
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_short() {
+        ::init();
+        let k = gen_key();
+        let n = gen_nonce();
+        for i in (0..TAGBYTES) {
+            let c = [0; TAGBYTES];
+            assert!(None == open(&c[..i], None, &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_vector_1() {
+        // Test Case 16 from "The Galois/Counter Mode of Operation (GCM)"
+        // by McGrew and Viega
+        ::init();
+        if !is_available() {
+            return;
+        }
+        let k = Key([0xfe, 0xff, 0xe9, 0x92, 0x86, 0x65, 0x73, 0x1c,
+                     0x6d, 0x6a, 0x8f, 0x94, 0x67, 0x30, 0x83, 0x08,
+                     0xfe, 0xff, 0xe9, 0x92, 0x86, 0x65, 0x73, 0x1c,
+                     0x6d, 0x6a, 0x8f, 0x94, 0x67, 0x30, 0x83, 0x08]);
+        let m = [0xd9, 0x31, 0x32, 0x25, 0xf8, 0x84, 0x06, 0xe5,
+                 0xa5, 0x59, 0x09, 0xc5, 0xaf, 0xf5, 0x26, 0x9a,
+                 0x86, 0xa7, 0xa9, 0x53, 0x15, 0x34, 0xf7, 0xda,
+                 0x2e, 0x4c, 0x30, 0x3d, 0x8a, 0x31, 0x8a, 0x72,
+                 0x1c, 0x3c, 0x0c, 0x95, 0x95, 0x68, 0x09, 0x53,
+                 0x2f, 0xcf, 0x0e, 0x24, 0x49, 0xa6, 0xb5, 0x25,
+                 0xb1, 0x6a, 0xed, 0xf5, 0xaa, 0x0d, 0xe6, 0x57,
+                 0xba, 0x63, 0x7b, 0x39];
+        let n = Nonce([0xca, 0xfe, 0xba, 0xbe, 0xfa, 0xce, 0xdb, 0xad,
+                       0xde, 0xca, 0xf8, 0x88]);
+        let ad = [0xfe, 0xed, 0xfa, 0xce, 0xde, 0xad, 0xbe, 0xef,
+                  0xfe, 0xed, 0xfa, 0xce, 0xde, 0xad, 0xbe, 0xef,
+                  0xab, 0xad, 0xda, 0xd2];
+        let c_expected = [0x52, 0x2d, 0xc1, 0xf0, 0x99, 0x56, 0x7d, 0x07,
+                          0xf4, 0x7f, 0x37, 0xa3, 0x2a, 0x84, 0x42, 0x7d,
+                          0x64, 0x3a, 0x8c, 0xdc, 0xbf, 0xe5, 0xc0, 0xc9,
+                          0x75, 0x98, 0xa2, 0xbd, 0x25, 0x55, 0xd1, 0xaa,
+                          0x8c, 0xb0, 0x8e, 0x48, 0x59, 0x0d, 0xbb, 0x3d,
+                          0xa7, 0xb0, 0x8b, 0x10, 0x56, 0x82, 0x88, 0x38,
+                          0xc5, 0xf6, 0x1e, 0x63, 0x93, 0xba, 0x7a, 0x0a,
+                          0xbc, 0xc9, 0xf6, 0x62];
+        let tag_expected = [0x76, 0xfc, 0x6e, 0xce, 0x0f, 0x4e, 0x17, 0x68,
+                            0xcd, 0xdf, 0x88, 0x53, 0xbb, 0x2d, 0x55, 0x1b];
+        let c = seal(&m, Some(&ad), &n, &k).unwrap();
+        assert!(&c[..m.len()] == &c_expected[..]);
+        assert!(&c[m.len()..] == &tag_expected[..]);
+        let m2 = open(&c, Some(&ad), &n, &k).unwrap();
+        assert!(&m2[..] == &m[..]);
+        let pk = precompute(&k).unwrap();
+        let (cd, Tag(tag)) = seal_detached_precomputed(&m, Some(&ad), &n, &pk);
+        assert!(&cd[..] == &c_expected[..]);
+        assert!(tag == tag_expected);
+    }
+
+    #[test]
+    fn test_serialisation() {
+        for _ in (0..256usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            round_trip(k);
+            round_trip(n);
+            round_trip(Tag([0; TAGBYTES]));
+        }
+    }
+}
+
+#[cfg(feature = "benchmarks")]
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open_precomputed(b: &mut test::Bencher) {
+        ::init();
+        let pk = precompute(&gen_key()).unwrap();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open_precomputed(&seal_precomputed(&m, None, &n, &pk), None, &n, &pk).unwrap();
+            }
+        });
+    }
+}
@@ -0,0 +1,74 @@
+//! Authenticated Encryption with Additional Data
+//!
+//! # Security model
+//! The `seal()` function encrypts a confidential message `m` and, in the same
+//! pass, computes an authenticator tag over both `m` and an optional
+//! non-confidential message `ad` (for example a packet header or a
+//! protocol version). The `ad` is not encrypted and is not part of the
+//! ciphertext, but it is required to successfully `open()` the ciphertext.
+//!
+//! A `Nonce` must never be reused with the same `Key`. Note that the nonces
+//! used by `chacha20poly1305` are only 64 bits long, so randomly generated
+//! nonces have a non-negligible risk of collision. Use a counter instead,
+//! for example nonce 1 for the first message, nonce 2 for the second
+//! message, etc. The 192-bit nonces used by `xchacha20poly1305_ietf` are
+//! long enough to be generated randomly with `gen_nonce()`.
+//!
+//! # Selected primitive
+//! `seal()` is currently `crypto_aead_chacha20poly1305`, the combination of
+//! the ChaCha20 stream cipher and the Poly1305 authenticator specified in
+//! [draft-agl-tls-chacha20poly1305](https://tools.ietf.org/html/draft-agl-tls-chacha20poly1305-04).
+//!
+//! # Alternate primitives
+//!
+//! ----------------------------------------------------------------------
+//! |crypto_aead                        |KEYBYTES|NONCEBYTES|TAGBYTES|
+//! |-----------------------------------|--------|----------|--------|
+//! |crypto_aead_aes256gcm              |32      |12        |16      |
+//! |crypto_aead_chacha20poly1305       |32      |8         |16      |
+//! |crypto_aead_chacha20poly1305_ietf  |32      |12        |16      |
+//! |crypto_aead_xchacha20poly1305_ietf |32      |24        |16      |
+//! ----------------------------------------------------------------------
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::aead;
+//! let key = aead::gen_key();
+//! let nonce = aead::gen_nonce();
+//! let plaintext = b"some data";
+//! let additional_data = b"some header";
+//! let ciphertext = aead::seal(plaintext, Some(additional_data), &nonce, &key);
+//! let their_plaintext = aead::open(&ciphertext, Some(additional_data),
+//!                                  &nonce, &key).unwrap();
+//! assert!(plaintext == &their_plaintext[..]);
+//! ```
+//!
+//! # Generic code
+//! The `Aead` trait is implemented by a marker type in each module, such as
+//! `chacha20poly1305::ChaCha20Poly1305` or `aes256gcm::Aes256Gcm`, so that code
+//! can be parameterised by the construction:
+//!
+//! ```
+//! use sodiumoxide::crypto::aead::Aead;
+//! use sodiumoxide::crypto::aead::xchacha20poly1305_ietf::{self, XChaCha20Poly1305Ietf};
+//!
+//! fn send_record<A: Aead>(record: &mut Vec<u8>, seq: u64, nonce: &A::Nonce, key: &A::Key) {
+//!     let header = [(seq >> 8) as u8, seq as u8];
+//!     A::seal_in_place(record, Some(&header), nonce, key).unwrap();
+//! }
+//!
+//! let key = xchacha20poly1305_ietf::gen_key();
+//! let nonce = xchacha20poly1305_ietf::gen_nonce();
+//! let mut record = b"some data".to_vec();
+//! send_record::<XChaCha20Poly1305Ietf>(&mut record, 1, &nonce, &key);
+//! assert!(record.len() == 9 + XChaCha20Poly1305Ietf::TAGBYTES);
+//! ```
+pub use self::chacha20poly1305::*;
+pub use self::traits::Aead;
+#[macro_use]
+mod aead_macros;
+mod traits;
+pub mod aes256gcm;
+pub mod chacha20poly1305;
+pub mod chacha20poly1305_ietf;
+pub mod xchacha20poly1305_ietf;
@@ -0,0 +1,160 @@
+/// The `Aead` trait abstracts over the authenticated encryption constructions,
+/// so that code can be written once and parameterised by the construction.
+///
+/// It is implemented by a marker type in each construction's module, for
+/// example `chacha20poly1305::ChaCha20Poly1305` or `aes256gcm::Aes256Gcm`, and
+/// by `secretbox::xsalsa20poly1305::XSalsa20Poly1305`, which does not support
+/// additional data.
+///
+/// The ciphertexts produced by `seal()` and `seal_in_place()` have the same
+/// layout as those produced by the `seal()` function of the construction's
+/// module.
+pub trait Aead {
+    /// The secret key type of the construction
+    type Key;
+    /// The nonce type of the construction
+    type Nonce;
+    /// The authentication tag type of the construction
+    type Tag;
+
+    /// Number of bytes in a `Key`
+    const KEYBYTES: usize;
+    /// Number of bytes in a `Nonce`
+    const NONCEBYTES: usize;
+    /// Number of bytes in a `Tag`, and by which a ciphertext is longer than
+    /// its message
+    const TAGBYTES: usize;
+
+    /// `key_from_slice()` creates a `Key` from a byte slice
+    ///
+    /// This function will fail and return `None` if the length of
+    /// the byte-slice isn't equal to `KEYBYTES`.
+    fn key_from_slice(bs: &[u8]) -> Option<Self::Key>;
+
+    /// `nonce_from_slice()` creates a `Nonce` from a byte slice
+    ///
+    /// This function will fail and return `None` if the length of
+    /// the byte-slice isn't equal to `NONCEBYTES`.
+    fn nonce_from_slice(bs: &[u8]) -> Option<Self::Nonce>;
+
+    /// `seal()` encrypts and authenticates a message `m` together with optional
+    /// plaintext data `ad` using a secret key `k` and a nonce `n`. It returns a
+    /// ciphertext `Ok(c)`.
+    ///
+    /// If the construction cannot be used, `seal()` returns `Err(())`: this is the
+    /// case for `Aes256Gcm` when the CPU lacks the required instructions, and for
+    /// `XSalsa20Poly1305` when `ad` is not empty.
+    fn seal(m: &[u8],
+            ad: Option<&[u8]>,
+            n: &Self::Nonce,
+            k: &Self::Key) -> Result<Vec<u8>, ()>;
+
+    /// `open()` verifies and decrypts a ciphertext `c` together with optional
+    /// plaintext data `ad` using a secret key `k` and a nonce `n`.
+    /// It returns a plaintext `Some(m)`.
+    /// If the ciphertext fails verification, or if the construction cannot be
+    /// used, `open()` returns `None`.
+    fn open(c: &[u8],
+            ad: Option<&[u8]>,
+            n: &Self::Nonce,
+            k: &Self::Key) -> Option<Vec<u8>>;
+
+    /// `seal_in_place()` encrypts and authenticates the message held in `buf`
+    /// together with optional plaintext data `ad` using a secret key `k` and a
+    /// nonce `n`. On return `buf` holds the ciphertext, laid out the same way as
+    /// the output of `seal()`.
+    ///
+    /// If the construction cannot be used, `seal_in_place()` returns `Err(())`
+    /// and leaves `buf` untouched.
+    fn seal_in_place(buf: &mut Vec<u8>,
+                     ad: Option<&[u8]>,
+                     n: &Self::Nonce,
+                     k: &Self::Key) -> Result<(), ()>;
+
+    /// `open_in_place()` verifies and decrypts a ciphertext `c`, as produced by
+    /// `seal()`, together with optional plaintext data `ad` using a secret key `k`
+    /// and a nonce `n`. The message is decrypted in place and `Ok(m)` is returned,
+    /// where `m` is the plaintext part of `c`.
+    ///
+    /// If the ciphertext fails verification, or if the construction cannot be
+    /// used, `open_in_place()` returns `Err(())`. The contents of `c` are
+    /// unspecified in that case.
+    fn open_in_place<'a>(c: &'a mut [u8],
+                         ad: Option<&[u8]>,
+                         n: &Self::Nonce,
+                         k: &Self::Key) -> Result<&'a [u8], ()>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::Aead;
+    use crypto::aead::{aes256gcm, chacha20poly1305, chacha20poly1305_ietf,
+                       xchacha20poly1305_ietf};
+    use crypto::secretbox::xsalsa20poly1305;
+    use randombytes::randombytes;
+
+    fn round_trip<A: Aead>(ad: Option<&[u8]>) {
+        let k = A::key_from_slice(&randombytes(A::KEYBYTES)).unwrap();
+        assert!(A::key_from_slice(&randombytes(A::KEYBYTES + 1)).is_none());
+        for i in (0..64usize) {
+            let n = A::nonce_from_slice(&randombytes(A::NONCEBYTES)).unwrap();
+            let m = randombytes(i);
+            let c = A::seal(&m, ad, &n, &k).unwrap();
+            assert!(c.len() == m.len() + A::TAGBYTES);
+            assert!(Some(m.clone()) == A::open(&c, ad, &n, &k));
+            let mut buf = m.clone();
+            A::seal_in_place(&mut buf, ad, &n, &k).unwrap();
+            assert!(buf == c);
+            assert!(Ok(&m[..]) == A::open_in_place(&mut buf, ad, &n, &k));
+            let mut c = c;
+            c[0] ^= 0x20;
+            assert!(None == A::open(&c, ad, &n, &k));
+            assert!(Err(()) == A::open_in_place(&mut c, ad, &n, &k));
+            assert!(Err(()) == A::open_in_place(&mut [], ad, &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_chacha20poly1305() {
+        round_trip::<chacha20poly1305::ChaCha20Poly1305>(Some(b"header"));
+        round_trip::<chacha20poly1305::ChaCha20Poly1305>(None);
+    }
+
+    #[test]
+    fn test_chacha20poly1305_ietf() {
+        round_trip::<chacha20poly1305_ietf::ChaCha20Poly1305Ietf>(Some(b"header"));
+        round_trip::<chacha20poly1305_ietf::ChaCha20Poly1305Ietf>(None);
+    }
+
+    #[test]
+    fn test_xchacha20poly1305_ietf() {
+        round_trip::<xchacha20poly1305_ietf::XChaCha20Poly1305Ietf>(Some(b"header"));
+        round_trip::<xchacha20poly1305_ietf::XChaCha20Poly1305Ietf>(None);
+    }
+
+    #[test]
+    fn test_aes256gcm() {
+        ::init();
+        if !aes256gcm::is_available() {
+            return;
+        }
+        round_trip::<aes256gcm::Aes256Gcm>(Some(b"header"));
+        round_trip::<aes256gcm::Aes256Gcm>(None);
+    }
+
+    #[test]
+    fn test_xsalsa20poly1305() {
+        use crypto::secretbox::xsalsa20poly1305::XSalsa20Poly1305;
+        round_trip::<XSalsa20Poly1305>(None);
+        round_trip::<XSalsa20Poly1305>(Some(&[]));
+        let k = xsalsa20poly1305::gen_key();
+        let n = xsalsa20poly1305::gen_nonce();
+        let mut buf = b"some data".to_vec();
+        assert!(XSalsa20Poly1305::seal(&buf, Some(b"header"), &n, &k).is_err());
+        assert!(XSalsa20Poly1305::seal_in_place(&mut buf, Some(b"header"), &n, &k).is_err());
+        assert!(&buf[..] == b"some data");
+        let mut c = XSalsa20Poly1305::seal(&buf, None, &n, &k).unwrap();
+        assert!(XSalsa20Poly1305::open(&c, Some(b"header"), &n, &k).is_none());
+        assert!(XSalsa20Poly1305::open_in_place(&mut c, Some(b"header"), &n, &k).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! The XChaCha20-Poly1305 construction, a variant of the IETF
+//! ChaCha20-Poly1305 construction with a 192-bit nonce.
+//!
+//! The nonce is long enough that it is safe to generate it randomly with
+//! `gen_nonce()` for every message, even when encrypting a very large number
+//! of messages with the same key.
+use ffi::{crypto_aead_xchacha20poly1305_ietf_encrypt,
+          crypto_aead_xchacha20poly1305_ietf_decrypt,
+          crypto_aead_xchacha20poly1305_ietf_encrypt_detached,
+          crypto_aead_xchacha20poly1305_ietf_decrypt_detached,
+          crypto_aead_xchacha20poly1305_ietf_KEYBYTES,
+          crypto_aead_xchacha20poly1305_ietf_NPUBBYTES,
+          crypto_aead_xchacha20poly1305_ietf_ABYTES};
+
+aead_module!(crypto_aead_xchacha20poly1305_ietf_encrypt,
+             crypto_aead_xchacha20poly1305_ietf_decrypt,
+             crypto_aead_xchacha20poly1305_ietf_encrypt_detached,
+             crypto_aead_xchacha20poly1305_ietf_decrypt_detached,
+             XChaCha20Poly1305Ietf,
+             crypto_aead_xchacha20poly1305_ietf_KEYBYTES,
+             crypto_aead_xchacha20poly1305_ietf_NPUBBYTES,
+             crypto_aead_xchacha20poly1305_ietf_ABYTES);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_1() {
+        // AEAD test vector taken from libsodium: test/default/aead_xchacha20poly1305.{c,exp}
+        let k = Key([0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+                     0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+                     0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+                     0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f]);
+        let m = b"Ladies and Gentlemen of the class of '99: If I could offer you \
+                  only one tip for the future, sunscreen would be it.";
+        let n = Nonce([0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43,
+                       0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x4b,
+                       0x4c, 0x4d, 0x4e, 0x4f, 0x50, 0x51, 0x52, 0x53]);
+        let ad = [0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3,
+                  0xc4, 0xc5, 0xc6, 0xc7];
+        let c_expected = [0xf8, 0xeb, 0xea, 0x48, 0x75, 0x04, 0x40, 0x66,
+                          0xfc, 0x16, 0x2a, 0x06, 0x04, 0xe1, 0x71, 0xfe,
+                          0xec, 0xfb, 0x3d, 0x20, 0x42, 0x52, 0x48, 0x56,
+                          0x3b, 0xcf, 0xd5, 0xa1, 0x55, 0xdc, 0xc4, 0x7b,
+                          0xbd, 0xa7, 0x0b, 0x86, 0xe5, 0xab, 0x9b, 0x55,
+                          0x00, 0x2b, 0xd1, 0x27, 0x4c, 0x02, 0xdb, 0x35,
+                          0x32, 0x1a, 0xcd, 0x7a, 0xf8, 0xb2, 0xe2, 0xd2,
+                          0x50, 0x15, 0xe1, 0x36, 0xb7, 0x67, 0x94, 0x58,
+                          0xe9, 0xf4, 0x32, 0x43, 0xbf, 0x71, 0x9d, 0x63,
+                          0x9b, 0xad, 0xb5, 0xfe, 0xac, 0x03, 0xf8, 0x0a,
+                          0x19, 0xa9, 0x6e, 0xf1, 0x0c, 0xb1, 0xd1, 0x53,
+                          0x33, 0xa8, 0x37, 0xb9, 0x09, 0x46, 0xba, 0x38,
+                          0x54, 0xee, 0x74, 0xda, 0x3f, 0x25, 0x85, 0xef,
+                          0xc7, 0xe1, 0xe1, 0x70, 0xe1, 0x7e, 0x15, 0xe5,
+                          0x63, 0xe7, 0x76, 0x01, 0xf4, 0xf8, 0x5c, 0xaf,
+                          0xa8, 0xe5, 0x87, 0x76, 0x14, 0xe1, 0x43, 0xe6,
+                          0x84, 0x20];
+        let c = seal(m, Some(&ad), &n, &k);
+        assert!(&c[..] == &c_expected[..]);
+        let m2 = open(&c, Some(&ad), &n, &k).unwrap();
+        assert!(&m2[..] == &m[..]);
+        let (cd, Tag(tag)) = seal_detached(m, Some(&ad), &n, &k);
+        assert!(&cd[..] == &c_expected[..m.len()]);
+        assert!(&tag[..] == &c_expected[m.len()..]);
+        let m3 = open_detached(&cd, Some(&ad), &Tag(tag), &n, &k).unwrap();
+        assert!(&m3[..] == &m[..]);
+    }
+}
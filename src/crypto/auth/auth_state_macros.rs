@@ -0,0 +1,123 @@
+macro_rules! auth_state (($state_name:ident,
+                          $init_name:ident,
+                          $update_name:ident,
+                          $final_name:ident,
+                          $tagbytes:expr) => (
+
+use ffi;
+use std::mem;
+use libc::size_t;
+
+/// `State` for incremental (streaming) authentication
+///
+/// A `State` allows authenticating messages that are too large to be kept
+/// in memory, or that arrive in pieces. Feed the message to `update()`, in
+/// as many calls as needed, and call `finalize()` to get the `Tag`.
+/// The result is the same as calling `authenticate()` on the concatenation
+/// of all the pieces.
+///
+/// When a `State` goes out of scope its contents, which are derived
+/// from the key, will be zeroed out
+pub struct State($state_name);
+
+impl Drop for State {
+    fn drop(&mut self) {
+        let &mut State(ref mut s) = self;
+        unsafe {
+            let sp: *mut $state_name = s;
+            ffi::sodium_memzero(sp as *mut u8, mem::size_of_val(s) as size_t);
+        }
+    }
+}
+
+impl State {
+    /// `init()` initializes a `State` for authenticating a message using
+    /// a secret key `k`.
+    pub fn init(&Key(ref k): &Key) -> State {
+        State::init_from_bytes(k)
+    }
+
+    /// `init_from_bytes()` initializes a `State` for authenticating a message
+    /// using a secret key `k` of arbitrary length.
+    ///
+    /// Keys longer than the block size of the underlying hash function are
+    /// hashed first, as specified for HMAC. The resulting tags can only be
+    /// checked with `verify()` if `k` is exactly `KEYBYTES` long.
+    pub fn init_from_bytes(k: &[u8]) -> State {
+        unsafe {
+            let mut s: $state_name = mem::zeroed();
+            $init_name(&mut s, k.as_ptr(), k.len() as size_t);
+            State(s)
+        }
+    }
+
+    /// `update()` authenticates the next piece of the message `m`
+    pub fn update(&mut self, m: &[u8]) {
+        let &mut State(ref mut s) = self;
+        unsafe {
+            $update_name(s, m.as_ptr(), m.len() as c_ulonglong);
+        }
+    }
+
+    /// `finalize()` completes the computation and returns the
+    /// authenticator `Tag`
+    pub fn finalize(mut self) -> Tag {
+        let &mut State(ref mut s) = &mut self;
+        let mut tag = [0; $tagbytes];
+        unsafe {
+            $final_name(s, &mut tag);
+        }
+        Tag(tag)
+    }
+}
+
+#[cfg(test)]
+mod test_s {
+    use super::*;
+
+    #[test]
+    fn test_auth_eq_auth_state() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let tag = authenticate(&m, &k);
+            let mut state = State::init(&k);
+            state.update(&m);
+            let tag2 = state.finalize();
+            assert!(tag == tag2);
+        }
+    }
+
+    #[test]
+    fn test_auth_eq_auth_state_chunked() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let tag = authenticate(&m, &k);
+            let mut state = State::init(&k);
+            for c in m.chunks(1) {
+                state.update(c);
+            }
+            let tag2 = state.finalize();
+            assert!(tag == tag2);
+        }
+    }
+
+    #[test]
+    fn test_auth_state_verify() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let mut state = State::init(&k);
+            for c in m.chunks(i / 3 + 1) {
+                state.update(c);
+            }
+            assert!(verify(&state.finalize(), &m, &k));
+        }
+    }
+}
+
+));
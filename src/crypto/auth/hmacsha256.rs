@@ -2,6 +2,10 @@
 //! unforgeability.
 use ffi::{crypto_auth_hmacsha256,
           crypto_auth_hmacsha256_verify,
+          crypto_auth_hmacsha256_state,
+          crypto_auth_hmacsha256_init,
+          crypto_auth_hmacsha256_update,
+          crypto_auth_hmacsha256_final,
           crypto_auth_hmacsha256_KEYBYTES,
           crypto_auth_hmacsha256_BYTES
 };
@@ -11,6 +15,12 @@ auth_module!(crypto_auth_hmacsha256,
              crypto_auth_hmacsha256_KEYBYTES,
              crypto_auth_hmacsha256_BYTES);
 
+auth_state!(crypto_auth_hmacsha256_state,
+            crypto_auth_hmacsha256_init,
+            crypto_auth_hmacsha256_update,
+            crypto_auth_hmacsha256_final,
+            crypto_auth_hmacsha256_BYTES);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -36,4 +46,19 @@ mod test {
         let a = authenticate(&c, &key);
         assert!(a == a_expected);
     }
+
+    #[test]
+    fn test_vector_state_long_key() {
+        // test case 6 from RFC 4231, with HMAC-SHA-256
+        let key = [0xaa; 131];
+        let m = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let a_expected = Tag([0x60,0xe4,0x31,0x59,0x1e,0xe0,0xb6,0x7f
+                             ,0x0d,0x8a,0x26,0xaa,0xcb,0xf5,0xb7,0x7f
+                             ,0x8e,0x0b,0xc6,0x21,0x37,0x28,0xc5,0x14
+                             ,0x05,0x46,0x04,0x0f,0x0e,0xe3,0x7f,0x54]);
+        let mut state = State::init_from_bytes(&key);
+        state.update(&m[..]);
+        let a = state.finalize();
+        assert!(a == a_expected);
+    }
 }
@@ -2,6 +2,10 @@
 //! unforgeability.
 use ffi::{crypto_auth_hmacsha512,
           crypto_auth_hmacsha512_verify,
+          crypto_auth_hmacsha512_state,
+          crypto_auth_hmacsha512_init,
+          crypto_auth_hmacsha512_update,
+          crypto_auth_hmacsha512_final,
           crypto_auth_hmacsha512_KEYBYTES,
           crypto_auth_hmacsha512_BYTES};
 
@@ -10,6 +14,12 @@ auth_module!(crypto_auth_hmacsha512,
              crypto_auth_hmacsha512_KEYBYTES,
              crypto_auth_hmacsha512_BYTES);
 
+auth_state!(crypto_auth_hmacsha512_state,
+            crypto_auth_hmacsha512_init,
+            crypto_auth_hmacsha512_update,
+            crypto_auth_hmacsha512_final,
+            crypto_auth_hmacsha512_BYTES);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -39,4 +49,23 @@ mod test {
         let Tag(a) = authenticate(&c, &key);
         assert!(a[..] == a_expected[..]);
     }
+
+    #[test]
+    fn test_vector_state_long_key() {
+        // test case 6 from RFC 4231, with HMAC-SHA-512
+        let key = [0xaa; 131];
+        let m = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let a_expected = Tag([0x80,0xb2,0x42,0x63,0xc7,0xc1,0xa3,0xeb
+                             ,0xb7,0x14,0x93,0xc1,0xdd,0x7b,0xe8,0xb4
+                             ,0x9b,0x46,0xd1,0xf4,0x1b,0x4a,0xee,0xc1
+                             ,0x12,0x1b,0x01,0x37,0x83,0xf8,0xf3,0x52
+                             ,0x6b,0x56,0xd0,0x37,0xe0,0x5f,0x25,0x98
+                             ,0xbd,0x0f,0xd2,0x21,0x5d,0x6a,0x1e,0x52
+                             ,0x95,0xe6,0x4f,0x73,0xf6,0x3f,0x0a,0xec
+                             ,0x8b,0x91,0x5a,0x98,0x5d,0x78,0x65,0x98]);
+        let mut state = State::init_from_bytes(&key);
+        state.update(&m[..]);
+        let a = state.finalize();
+        assert!(a == a_expected);
+    }
 }
@@ -3,6 +3,10 @@
 //! of unforgeability.
 use ffi::{crypto_auth_hmacsha512256,
           crypto_auth_hmacsha512256_verify,
+          crypto_auth_hmacsha512256_state,
+          crypto_auth_hmacsha512256_init,
+          crypto_auth_hmacsha512256_update,
+          crypto_auth_hmacsha512256_final,
           crypto_auth_hmacsha512256_KEYBYTES,
           crypto_auth_hmacsha512256_BYTES};
 
@@ -11,6 +15,12 @@ auth_module!(crypto_auth_hmacsha512256,
              crypto_auth_hmacsha512256_KEYBYTES,
              crypto_auth_hmacsha512256_BYTES);
 
+auth_state!(crypto_auth_hmacsha512256_state,
+            crypto_auth_hmacsha512256_init,
+            crypto_auth_hmacsha512256_update,
+            crypto_auth_hmacsha512256_final,
+            crypto_auth_hmacsha512256_BYTES);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -36,4 +46,19 @@ mod test {
         let Tag(a) = authenticate(&c, &key);
         assert!(a == a_expected);
     }
+
+    #[test]
+    fn test_vector_state_long_key() {
+        // test case 6 from RFC 4231, with the first 256 bits of HMAC-SHA-512
+        let key = [0xaa; 131];
+        let m = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let a_expected = Tag([0x80,0xb2,0x42,0x63,0xc7,0xc1,0xa3,0xeb
+                             ,0xb7,0x14,0x93,0xc1,0xdd,0x7b,0xe8,0xb4
+                             ,0x9b,0x46,0xd1,0xf4,0x1b,0x4a,0xee,0xc1
+                             ,0x12,0x1b,0x01,0x37,0x83,0xf8,0xf3,0x52]);
+        let mut state = State::init_from_bytes(&key);
+        state.update(&m[..]);
+        let a = state.finalize();
+        assert!(a == a_expected);
+    }
 }
@@ -42,9 +42,23 @@
 //! let tag = auth::authenticate(data_to_authenticate, &key);
 //! assert!(auth::verify(&tag, data_to_authenticate, &key));
 //! ```
+//!
+//! # Example (streaming)
+//! ```
+//! use sodiumoxide::crypto::auth;
+//!
+//! let key = auth::gen_key();
+//! let mut state = auth::State::init(&key);
+//! state.update(b"some data");
+//! state.update(b"some more data");
+//! let tag = state.finalize();
+//! assert!(auth::verify(&tag, b"some datasome more data", &key));
+//! ```
 pub use self::hmacsha512256::*;
 #[macro_use]
 mod auth_macros;
+#[macro_use]
+mod auth_state_macros;
 pub mod hmacsha512;
 pub mod hmacsha512256;
 pub mod hmacsha256;
@@ -5,16 +5,17 @@
 //! This function is conjectured to meet the standard notions of privacy and
 //! third-party unforgeability.
 use ffi;
-use marshal::marshal;
+use libc::c_ulonglong;
 use randombytes::randombytes_into;
 use rustc_serialize;
+use std::iter::repeat;
+use std::ptr;
 
 pub const PUBLICKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES;
 pub const SECRETKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES;
 pub const NONCEBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_NONCEBYTES;
 pub const PRECOMPUTEDKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES;
-const ZEROBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_ZEROBYTES;
-const BOXZEROBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_BOXZEROBYTES;
+pub const MACBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_MACBYTES;
 
 /// `PublicKey` for asymmetric authenticated encryption
 #[derive(Copy)]
@@ -33,6 +34,19 @@ newtype_drop!(SecretKey);
 newtype_clone!(SecretKey);
 newtype_impl!(SecretKey, SECRETKEYBYTES);
 
+impl SecretKey {
+    /// `public_key()` computes the `PublicKey` corresponding to the secret
+    /// key, by multiplying the Curve25519 base point by it.
+    pub fn public_key(&self) -> PublicKey {
+        let &SecretKey(ref sk) = self;
+        let mut pk = [0u8; PUBLICKEYBYTES];
+        unsafe {
+            ffi::crypto_scalarmult_curve25519_base(&mut pk, sk);
+        }
+        PublicKey(pk)
+    }
+}
+
 /// `Nonce` for asymmetric authenticated encryption
 #[derive(Copy)]
 pub struct Nonce(pub [u8; NONCEBYTES]);
@@ -40,6 +54,14 @@ pub struct Nonce(pub [u8; NONCEBYTES]);
 newtype_clone!(Nonce);
 newtype_impl!(Nonce, NONCEBYTES);
 
+/// Authentication `Tag` for the detached form of asymmetric authenticated
+/// encryption
+#[derive(Copy)]
+pub struct Tag(pub [u8; MACBYTES]);
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, MACBYTES);
+
 /// `gen_keypair()` randomly generates a secret key and a corresponding public key.
 ///
 /// THREAD SAFETY: `gen_keypair()` is thread-safe provided that you have
@@ -69,20 +91,22 @@ pub fn gen_nonce() -> Nonce {
 
 /// `seal()` encrypts and authenticates a message `m` using the senders secret key `sk`,
 /// the receivers public key `pk` and a nonce `n`. It returns a ciphertext `c`.
+///
+/// The ciphertext is the authentication tag followed by the encrypted
+/// message, so it is `MACBYTES` longer than `m`.
 pub fn seal(m: &[u8],
             &Nonce(ref n): &Nonce,
             &PublicKey(ref pk): &PublicKey,
             &SecretKey(ref sk): &SecretKey) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305(dst,
-                                                       src,
-                                                       len,
-                                                       n,
-                                                       pk,
-                                                       sk);
-        }
-    });
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        ffi::crypto_box_easy(c.as_mut_ptr(),
+                             m.as_ptr(),
+                             m.len() as c_ulonglong,
+                             n,
+                             pk,
+                             sk);
+    }
     c
 }
 
@@ -93,19 +117,124 @@ pub fn open(c: &[u8],
             &Nonce(ref n): &Nonce,
             &PublicKey(ref pk): &PublicKey,
             &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
+    if c.len() < MACBYTES {
         return None;
     }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_open(dst,
-                                                            src,
-                                                            len,
-                                                            n,
-                                                            pk,
-                                                            sk)
-        }
-    });
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        ffi::crypto_box_open_easy(m.as_mut_ptr(),
+                                  c.as_ptr(),
+                                  c.len() as c_ulonglong,
+                                  n,
+                                  pk,
+                                  sk)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_inplace()` encrypts and authenticates the message held in `buf` using the
+/// senders secret key `sk`, the receivers public key `pk` and a nonce `n`. On return
+/// `buf` holds the ciphertext, laid out the same way as the output of `seal()`.
+///
+/// `buf` is only reallocated if its capacity is less than `MACBYTES` more than
+/// its length.
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &PublicKey(ref pk): &PublicKey,
+                    &SecretKey(ref sk): &SecretKey) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        let p = buf.as_mut_ptr();
+        let pm = p.offset(MACBYTES as isize);
+        ptr::copy(p, pm, mlen);
+        ffi::crypto_box_detached(pm,
+                                 p as *mut [u8; MACBYTES],
+                                 pm,
+                                 mlen as c_ulonglong,
+                                 n,
+                                 pk,
+                                 sk);
+    }
+}
+
+/// `open_inplace()` verifies and decrypts a ciphertext `c`, as produced by `seal()`,
+/// using the receiver's secret key `sk`, the senders public key `pk`, and a nonce `n`.
+/// The message is decrypted in place and `Ok(m)` is returned, where `m` is the
+/// plaintext part of `c`.
+/// If the ciphertext fails verification, `open_inplace()` returns `Err(())` and
+/// leaves `c` untouched.
+pub fn open_inplace<'a>(c: &'a mut [u8],
+                        &Nonce(ref n): &Nonce,
+                        &PublicKey(ref pk): &PublicKey,
+                        &SecretKey(ref sk): &SecretKey) -> Result<&'a [u8], ()> {
+    if c.len() < MACBYTES {
+        return Err(());
+    }
+    let ret = unsafe {
+        let p = c.as_mut_ptr();
+        let pc = p.offset(MACBYTES as isize);
+        ffi::crypto_box_open_detached(pc,
+                                      pc,
+                                      p as *const [u8; MACBYTES],
+                                      (c.len() - MACBYTES) as c_ulonglong,
+                                      n,
+                                      pk,
+                                      sk)
+    };
+    if ret == 0 {
+        Ok(&c[MACBYTES..])
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_detached()` encrypts and authenticates a message `m` using the senders secret
+/// key `sk`, the receivers public key `pk` and a nonce `n`. It returns the encrypted
+/// message `c`, which has the same length as `m`, and the authentication tag separately.
+///
+/// `seal()` returns the tag followed by `c`.
+pub fn seal_detached(m: &[u8],
+                     &Nonce(ref n): &Nonce,
+                     &PublicKey(ref pk): &PublicKey,
+                     &SecretKey(ref sk): &SecretKey) -> (Vec<u8>, Tag) {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        ffi::crypto_box_detached(c.as_mut_ptr(),
+                                 &mut tag,
+                                 m.as_ptr(),
+                                 m.len() as c_ulonglong,
+                                 n,
+                                 pk,
+                                 sk);
+    }
+    (c, Tag(tag))
+}
+
+/// `open_detached()` verifies and decrypts an encrypted message `c` and its
+/// authentication tag `tag` using the receiver's secret key `sk`, the senders
+/// public key `pk`, and a nonce `n`. It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_detached()` returns `None`.
+pub fn open_detached(c: &[u8],
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &PublicKey(ref pk): &PublicKey,
+                     &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        ffi::crypto_box_open_detached(m.as_mut_ptr(),
+                                      c.as_ptr(),
+                                      tag,
+                                      c.len() as c_ulonglong,
+                                      n,
+                                      pk,
+                                      sk)
+    };
     if ret == 0 {
         Some(m)
     } else {
@@ -143,15 +272,14 @@ pub fn precompute(&PublicKey(ref pk): &PublicKey,
 pub fn seal_precomputed(m: &[u8],
                         &Nonce(ref n): &Nonce,
                         &PrecomputedKey(ref k): &PrecomputedKey) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_afternm(dst,
-                                                               src,
-                                                               len,
-                                                               n,
-                                                               k);
-        }
-    });
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        ffi::crypto_box_easy_afternm(c.as_mut_ptr(),
+                                     m.as_ptr(),
+                                     m.len() as c_ulonglong,
+                                     n,
+                                     k);
+    }
     c
 }
 
@@ -161,18 +289,113 @@ pub fn seal_precomputed(m: &[u8],
 pub fn open_precomputed(c: &[u8],
                         &Nonce(ref n): &Nonce,
                         &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
+    if c.len() < MACBYTES {
         return None;
     }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_open_afternm(dst,
-                                                                    src,
-                                                                    len,
-                                                                    n,
-                                                                    k)
-        }
-    });
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        ffi::crypto_box_open_easy_afternm(m.as_mut_ptr(),
+                                          c.as_ptr(),
+                                          c.len() as c_ulonglong,
+                                          n,
+                                          k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_precomputed_inplace()` encrypts and authenticates the message held in `buf`
+/// using a precomputed key `k` and a nonce `n`. On return `buf` holds the ciphertext,
+/// laid out the same way as the output of `seal_precomputed()`.
+///
+/// `buf` is only reallocated if its capacity is less than `MACBYTES` more than
+/// its length.
+pub fn seal_precomputed_inplace(buf: &mut Vec<u8>,
+                                &Nonce(ref n): &Nonce,
+                                &PrecomputedKey(ref k): &PrecomputedKey) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        let p = buf.as_mut_ptr();
+        let pm = p.offset(MACBYTES as isize);
+        ptr::copy(p, pm, mlen);
+        ffi::crypto_box_detached_afternm(pm,
+                                         p as *mut [u8; MACBYTES],
+                                         pm,
+                                         mlen as c_ulonglong,
+                                         n,
+                                         k);
+    }
+}
+
+/// `open_precomputed_inplace()` verifies and decrypts a ciphertext `c`, as produced by
+/// `seal_precomputed()`, using a precomputed key `k` and a nonce `n`. The message is
+/// decrypted in place and `Ok(m)` is returned, where `m` is the plaintext part of `c`.
+/// If the ciphertext fails verification, `open_precomputed_inplace()` returns `Err(())`
+/// and leaves `c` untouched.
+pub fn open_precomputed_inplace<'a>(c: &'a mut [u8],
+                                    &Nonce(ref n): &Nonce,
+                                    &PrecomputedKey(ref k): &PrecomputedKey)
+                                    -> Result<&'a [u8], ()> {
+    if c.len() < MACBYTES {
+        return Err(());
+    }
+    let ret = unsafe {
+        let p = c.as_mut_ptr();
+        let pc = p.offset(MACBYTES as isize);
+        ffi::crypto_box_open_detached_afternm(pc,
+                                              pc,
+                                              p as *const [u8; MACBYTES],
+                                              (c.len() - MACBYTES) as c_ulonglong,
+                                              n,
+                                              k)
+    };
+    if ret == 0 {
+        Ok(&c[MACBYTES..])
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_detached_precomputed()` encrypts and authenticates a message `m` using a
+/// precomputed key `k` and a nonce `n`. It returns the encrypted message `c` and
+/// the authentication tag separately.
+pub fn seal_detached_precomputed(m: &[u8],
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> (Vec<u8>, Tag) {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        ffi::crypto_box_detached_afternm(c.as_mut_ptr(),
+                                         &mut tag,
+                                         m.as_ptr(),
+                                         m.len() as c_ulonglong,
+                                         n,
+                                         k);
+    }
+    (c, Tag(tag))
+}
+
+/// `open_detached_precomputed()` verifies and decrypts an encrypted message `c` and
+/// its authentication tag `tag` using a precomputed key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_detached_precomputed()` returns `None`.
+pub fn open_detached_precomputed(c: &[u8],
+                                 &Tag(ref tag): &Tag,
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        ffi::crypto_box_open_detached_afternm(m.as_mut_ptr(),
+                                              c.as_ptr(),
+                                              tag,
+                                              c.len() as c_ulonglong,
+                                              n,
+                                              k)
+    };
     if ret == 0 {
         Some(m)
     } else {
@@ -254,6 +477,190 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_seal_open_detached() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let (c, tag) = seal_detached(&m, &n, &pk1, &sk2);
+            assert!(c.len() == m.len());
+            let opened = open_detached(&c, &tag, &n, &pk2, &sk1);
+            assert!(Some(m) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached_precomputed() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let k1 = precompute(&pk1, &sk2);
+            let k2 = precompute(&pk2, &sk1);
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let (c, tag) = seal_detached_precomputed(&m, &n, &k1);
+            let opened = open_detached_precomputed(&c, &tag, &n, &k2);
+            assert!(Some(m) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_detached_same_as_seal() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk1, _) = gen_keypair();
+            let (_, sk2) = gen_keypair();
+            let k = precompute(&pk1, &sk2);
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let c = seal(&m, &n, &pk1, &sk2);
+            let (cd, Tag(tag)) = seal_detached(&m, &n, &pk1, &sk2);
+            assert!(&c[..MACBYTES] == &tag[..]);
+            assert!(&c[MACBYTES..] == &cd[..]);
+            let (cdp, Tag(tagp)) = seal_detached_precomputed(&m, &n, &k);
+            assert!(tag == tagp);
+            assert!(cd == cdp);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let (mut c, Tag(mut tag)) = seal_detached(&m, &n, &pk1, &sk2);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open_detached(&c, &Tag(tag), &n, &pk2, &sk1));
+                c[j] ^= 0x20;
+            }
+            for j in (0..tag.len()) {
+                tag[j] ^= 0x20;
+                assert!(None == open_detached(&c, &Tag(tag), &n, &pk2, &sk1));
+                tag[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seal_open_inplace() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let mut buf = m.clone();
+            seal_inplace(&mut buf, &n, &pk1, &sk2);
+            assert!(buf == seal(&m, &n, &pk1, &sk2));
+            let opened = open_inplace(&mut buf, &n, &pk2, &sk1);
+            assert!(Ok(&m[..]) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_precomputed_inplace() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let k1 = precompute(&pk1, &sk2);
+            let k2 = precompute(&pk2, &sk1);
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let mut buf = m.clone();
+            seal_precomputed_inplace(&mut buf, &n, &k1);
+            assert!(buf == seal_precomputed(&m, &n, &k1));
+            let opened = open_precomputed_inplace(&mut buf, &n, &k2);
+            assert!(Ok(&m[..]) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_inplace_no_realloc() {
+        let (pk, sk) = gen_keypair();
+        let n = gen_nonce();
+        let mut buf = Vec::with_capacity(64 + MACBYTES);
+        buf.extend(b"some data".iter().cloned());
+        let p = buf.as_ptr();
+        seal_inplace(&mut buf, &n, &pk, &sk);
+        assert!(p == buf.as_ptr());
+    }
+
+    #[test]
+    fn test_open_inplace_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let (pk1, sk1) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let k2 = precompute(&pk2, &sk1);
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let mut c = seal(&m, &n, &pk1, &sk2);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                let c2 = c.clone();
+                assert!(Err(()) == open_inplace(&mut c, &n, &pk2, &sk1));
+                assert!(Err(()) == open_precomputed_inplace(&mut c, &n, &k2));
+                assert!(c == c2);
+                c[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_short() {
+        let (pk, sk) = gen_keypair();
+        let k = precompute(&pk, &sk);
+        let n = gen_nonce();
+        for i in (0..MACBYTES) {
+            let c: Vec<u8> = (0..i).map(|x| x as u8).collect();
+            assert!(None == open(&c, &n, &pk, &sk));
+            assert!(None == open_precomputed(&c, &n, &k));
+            let mut c = c;
+            assert!(Err(()) == open_inplace(&mut c, &n, &pk, &sk));
+            assert!(Err(()) == open_precomputed_inplace(&mut c, &n, &k));
+        }
+    }
+
+    #[test]
+    fn test_public_key() {
+        for _ in (0..64usize) {
+            let (pk, sk) = gen_keypair();
+            assert!(sk.public_key() == pk);
+        }
+    }
+
+    #[test]
+    fn test_public_key_vector() {
+        // corresponding to tests/box.c and tests/box3.cpp from NaCl
+        let alicesk = SecretKey([0x77,0x07,0x6d,0x0a,0x73,0x18,0xa5,0x7d,
+                                 0x3c,0x16,0xc1,0x72,0x51,0xb2,0x66,0x45,
+                                 0xdf,0x4c,0x2f,0x87,0xeb,0xc0,0x99,0x2a,
+                                 0xb1,0x77,0xfb,0xa5,0x1d,0xb9,0x2c,0x2a]);
+        let alicepk = PublicKey([0x85,0x20,0xf0,0x09,0x89,0x30,0xa7,0x54,
+                                 0x74,0x8b,0x7d,0xdc,0xb4,0x3e,0xf7,0x5a,
+                                 0x0d,0xbf,0x3a,0x0d,0x26,0x38,0x1a,0xf4,
+                                 0xeb,0xa4,0xa9,0x8e,0xaa,0x9b,0x4e,0x6a]);
+        let bobsk   = SecretKey([0x5d,0xab,0x08,0x7e,0x62,0x4a,0x8a,0x4b,
+                                 0x79,0xe1,0x7f,0x8b,0x83,0x80,0x0e,0xe6,
+                                 0x6f,0x3b,0xb1,0x29,0x26,0x18,0xb6,0xfd,
+                                 0x1c,0x2f,0x8b,0x27,0xff,0x88,0xe0,0xeb]);
+        let bobpk   = PublicKey([0xde,0x9e,0xdb,0x7d,0x7b,0x7d,0xc1,0xb4,
+                                 0xd3,0x5b,0x61,0xc2,0xec,0xe4,0x35,0x37,
+                                 0x3f,0x83,0x43,0xc8,0x5b,0x78,0x67,0x4d,
+                                 0xad,0xfc,0x7e,0x14,0x6f,0x88,0x2b,0x4f]);
+        assert!(alicesk.public_key() == alicepk);
+        assert!(bobsk.public_key() == bobpk);
+    }
+
     #[test]
     fn test_vector_1() {
         // corresponding to tests/box.c and tests/box3.cpp from NaCl
@@ -309,6 +716,9 @@ mod test {
                         0xe3,0x55,0xa5];
         assert!(c == cexp);
         assert!(cpre == cexp);
+        let (cd, Tag(tag)) = seal_detached(&m, &nonce, &bobpk, &alicesk);
+        assert!(&tag[..] == &cexp[..MACBYTES]);
+        assert!(&cd[..] == &cexp[MACBYTES..]);
     }
 
     #[test]
@@ -366,6 +776,9 @@ mod test {
         let m_pre = open_precomputed(&c, &nonce, &pk);
         assert!(m == mexp);
         assert!(m_pre == mexp);
+        let tag = Tag::from_slice(&c[..MACBYTES]).unwrap();
+        let m_det = open_detached(&c[MACBYTES..], &tag, &nonce, &alicepk, &bobsk);
+        assert!(m_det == mexp);
     }
 
     #[test]
@@ -373,9 +786,11 @@ mod test {
         for _ in (0..256usize) {
             let (pk, sk) = gen_keypair();
             let n = gen_nonce();
+            let (_, tag) = seal_detached(&[], &n, &pk, &sk);
             round_trip(pk);
             round_trip(sk);
             round_trip(n);
+            round_trip(tag);
         }
     }
 }
@@ -404,6 +819,39 @@ mod bench {
         });
     }
 
+    #[bench]
+    fn bench_seal_open_inplace(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            let mut m = Vec::with_capacity(*s + MACBYTES);
+            m.extend(randombytes(*s).into_iter());
+            m
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                seal_inplace(m, &n, &pk, &sk);
+                open_inplace(m, &n, &pk, &sk).unwrap();
+                m.drain(..MACBYTES);
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                let (c, tag) = seal_detached(m, &n, &pk, &sk);
+                open_detached(&c, &tag, &n, &pk, &sk).unwrap();
+            }
+        });
+    }
+
     #[bench]
     fn bench_precompute(b: &mut test::Bencher) {
         let (pk, sk) = gen_keypair();
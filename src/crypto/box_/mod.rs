@@ -73,5 +73,18 @@
 //!                                              &their_precomputed_key).unwrap();
 //! assert!(plaintext == &their_plaintext[..]);
 //! ```
+//! # Example (sealed boxes)
+//! ```
+//! use sodiumoxide::crypto::box_;
+//! use sodiumoxide::crypto::box_::sealedbox;
+//!
+//! let (theirpk, theirsk) = box_::gen_keypair();
+//! let plaintext = b"some data";
+//! // no secret key or nonce is needed on the sender side
+//! let ciphertext = sealedbox::seal(plaintext, &theirpk);
+//! let their_plaintext = sealedbox::open(&ciphertext, &theirpk, &theirsk).unwrap();
+//! assert!(plaintext == &their_plaintext[..]);
+//! ```
 pub use self::curve25519xsalsa20poly1305::*;
 pub mod curve25519xsalsa20poly1305;
+pub mod sealedbox;
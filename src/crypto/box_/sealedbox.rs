@@ -0,0 +1,158 @@
+//! Sealed boxes: anonymous public-key encryption
+//!
+//! Sealed boxes are designed to anonymously send messages to a recipient
+//! given its public key.
+//!
+//! Only the recipient can decrypt these messages, using its secret key.
+//! While the recipient can verify the integrity of the message, it cannot
+//! verify the identity of the sender.
+//!
+//! A message is encrypted using an ephemeral key pair, whose secret part
+//! is destroyed right after the encryption process. Without knowing the
+//! secret key used for a given message, the sender cannot decrypt its own
+//! message later. And without additional data, a message cannot be
+//! correlated with the identity of its sender.
+//!
+//! The nonce is derived from the ephemeral public key and the recipient's
+//! public key, so no nonce has to be supplied by the caller.
+//!
+//! # Selected primitive
+//! `seal()` is `crypto_box_seal`, which uses the same
+//! `crypto_box_curve25519xsalsa20poly1305` construction as `box_::seal()`,
+//! with the nonce computed as `BLAKE2b(ephemeral_pk || recipient_pk)`.
+use ffi;
+use libc::c_ulonglong;
+use std::iter::repeat;
+use super::curve25519xsalsa20poly1305::{PublicKey, SecretKey};
+
+/// Number of bytes a sealed box adds to the length of the plaintext
+pub const SEALBYTES: usize = ffi::crypto_box_SEALBYTES;
+
+/// `seal()` encrypts a message `m` for the receiver whose public key is `pk`.
+/// It returns the ciphertext `c`, which is `SEALBYTES` longer than `m`.
+///
+/// THREAD SAFETY: `seal()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn seal(m: &[u8],
+            &PublicKey(ref pk): &PublicKey) -> Vec<u8> {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + SEALBYTES).collect();
+    unsafe {
+        ffi::crypto_box_seal(c.as_mut_ptr(),
+                             m.as_ptr(),
+                             m.len() as c_ulonglong,
+                             pk);
+    }
+    c
+}
+
+/// `open()` decrypts a ciphertext `c` using the receiver's public key `pk`
+/// and secret key `sk`. It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open()` returns `None`.
+pub fn open(c: &[u8],
+            &PublicKey(ref pk): &PublicKey,
+            &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
+    if c.len() < SEALBYTES {
+        return None;
+    }
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - SEALBYTES).collect();
+    let ret = unsafe {
+        ffi::crypto_box_seal_open(m.as_mut_ptr(),
+                                  c.as_ptr(),
+                                  c.len() as c_ulonglong,
+                                  pk,
+                                  sk)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crypto::box_::curve25519xsalsa20poly1305::gen_keypair;
+
+    #[test]
+    fn test_seal_open() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let (pk, sk) = gen_keypair();
+            let m = randombytes(i);
+            let c = seal(&m, &pk);
+            assert!(c.len() == m.len() + SEALBYTES);
+            let opened = open(&c, &pk, &sk);
+            assert!(Some(m) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let (pk, sk) = gen_keypair();
+            let m = randombytes(i);
+            let mut c = seal(&m, &pk);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open(&c, &pk, &sk));
+                c[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seal_open_wrong_key() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let (pk1, _) = gen_keypair();
+            let (pk2, sk2) = gen_keypair();
+            let m = randombytes(i);
+            let c = seal(&m, &pk1);
+            assert!(None == open(&c, &pk2, &sk2));
+        }
+    }
+
+    #[test]
+    fn test_open_short() {
+        let (pk, sk) = gen_keypair();
+        for i in (0..SEALBYTES) {
+            let c: Vec<u8> = (0..i).map(|x| x as u8).collect();
+            assert!(None == open(&c, &pk, &sk));
+        }
+    }
+
+    #[test]
+    fn test_seal_randomized() {
+        let (pk, _) = gen_keypair();
+        let m = b"some data";
+        assert!(seal(m, &pk) != seal(m, &pk));
+    }
+}
+
+#[cfg(feature = "benchmarks")]
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use crypto::box_::curve25519xsalsa20poly1305::gen_keypair;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(m, &pk), &pk, &sk).unwrap();
+            }
+        });
+    }
+}
@@ -0,0 +1,468 @@
+//! `BLAKE2b`, a cryptographic hash function specified in
+//! [RFC 7693](https://tools.ietf.org/html/rfc7693). It is faster than `MD5`,
+//! `SHA-1`, `SHA-2` and `SHA-3`, yet is at least as secure as `SHA-3`.
+use ffi;
+use libc::{c_ulonglong, size_t};
+use std::{io, mem, ptr};
+use rustc_serialize;
+
+pub const HASHBYTES_MIN: usize = ffi::crypto_generichash_blake2b_BYTES_MIN;
+pub const HASHBYTES_MAX: usize = ffi::crypto_generichash_blake2b_BYTES_MAX;
+pub const HASHBYTES: usize = ffi::crypto_generichash_blake2b_BYTES;
+pub const KEYBYTES_MIN: usize = ffi::crypto_generichash_blake2b_KEYBYTES_MIN;
+pub const KEYBYTES_MAX: usize = ffi::crypto_generichash_blake2b_KEYBYTES_MAX;
+pub const KEYBYTES: usize = ffi::crypto_generichash_blake2b_KEYBYTES;
+pub const SALTBYTES: usize = ffi::crypto_generichash_blake2b_SALTBYTES;
+pub const PERSONALBYTES: usize = ffi::crypto_generichash_blake2b_PERSONALBYTES;
+
+/// Digest-structure
+///
+/// A `Digest` can be anywhere between `HASHBYTES_MIN` and `HASHBYTES_MAX`
+/// bytes long. It implements the traits `PartialEq` and `Eq` using
+/// constant-time comparison functions.
+#[derive(Copy)]
+pub struct Digest {
+    len: usize,
+    data: [u8; HASHBYTES_MAX],
+}
+
+impl Digest {
+    /// `from_slice()` creates a `Digest` from a byte slice
+    ///
+    /// This function will fail and return None if the length of
+    /// the byte-slice isn't between `HASHBYTES_MIN` and `HASHBYTES_MAX`
+    pub fn from_slice(bs: &[u8]) -> Option<Digest> {
+        if bs.len() < HASHBYTES_MIN || bs.len() > HASHBYTES_MAX {
+            return None;
+        }
+        let mut d = Digest { len: bs.len(), data: [0; HASHBYTES_MAX] };
+        for (di, &bsi) in d.data.iter_mut().zip(bs.iter()) {
+            *di = bsi
+        }
+        Some(d)
+    }
+
+    /// `len()` returns the length of the `Digest` in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Clone for Digest {
+    fn clone(&self) -> Digest {
+        *self
+    }
+}
+
+impl PartialEq for Digest {
+    fn eq(&self, other: &Digest) -> bool {
+        use crypto::verify::safe_memcmp;
+        safe_memcmp(&self[..], &other[..])
+    }
+}
+
+impl Eq for Digest {}
+
+impl rustc_serialize::Encodable for Digest {
+    fn encode<E: rustc_serialize::Encoder>(&self, encoder: &mut E)
+            -> Result<(), E::Error> {
+        encoder.emit_seq(self.len, |encoder| {
+            for (i, e) in self[..].iter().enumerate() {
+                try!(encoder.emit_seq_elt(i, |encoder| e.encode(encoder)))
+            }
+            Ok(())
+        })
+    }
+}
+
+impl rustc_serialize::Decodable for Digest {
+    fn decode<D: rustc_serialize::Decoder>(decoder: &mut D)
+            -> Result<Digest, D::Error> {
+        decoder.read_seq(|decoder, len| {
+            if len < HASHBYTES_MIN || len > HASHBYTES_MAX {
+                return Err(decoder.error(
+                    &format!("Expecting array of length between {} and {}, but found {}",
+                             HASHBYTES_MIN, HASHBYTES_MAX, len)));
+            }
+            let mut res = Digest { len: len, data: [0; HASHBYTES_MAX] };
+            for (i, val) in res.data[..len].iter_mut().enumerate() {
+                *val = try!(decoder.read_seq_elt(i,
+                    |decoder| rustc_serialize::Decodable::decode(decoder)));
+            }
+            Ok(res)
+        })
+    }
+}
+
+newtype_varlen_index!(Digest, x => x.data[..x.len]);
+
+/// `Salt` for the `hash_salt_personal()` function
+#[derive(Copy)]
+pub struct Salt(pub [u8; SALTBYTES]);
+
+newtype_clone!(Salt);
+newtype_impl!(Salt, SALTBYTES);
+
+/// `Personal` (personalization string) for the `hash_salt_personal()` function
+#[derive(Copy)]
+pub struct Personal(pub [u8; PERSONALBYTES]);
+
+newtype_clone!(Personal);
+newtype_impl!(Personal, PERSONALBYTES);
+
+/// `check_params()` returns `true` if `outlen` is a valid digest length
+/// and `key`, if present, has a valid length
+fn check_params(outlen: usize, key: Option<&[u8]>) -> bool {
+    if outlen < HASHBYTES_MIN || outlen > HASHBYTES_MAX {
+        return false;
+    }
+    match key {
+        Some(k) => k.len() >= KEYBYTES_MIN && k.len() <= KEYBYTES_MAX,
+        None => true,
+    }
+}
+
+/// `hash()` hashes a message `m` into an `outlen`-byte `Digest`,
+/// optionally under a secret key `key`.
+///
+/// `outlen` must be between `HASHBYTES_MIN` and `HASHBYTES_MAX` bytes.
+/// `HASHBYTES` is the recommended default.
+///
+/// A `key` is only needed if the same message has to be hashed into
+/// different, unlinkable digests, or if the digest is used as a MAC.
+/// If present, it must be between `KEYBYTES_MIN` and `KEYBYTES_MAX` bytes.
+///
+/// The function returns `Err(())` if `outlen` or the length of `key`
+/// are out of range.
+pub fn hash(m: &[u8], outlen: usize, key: Option<&[u8]>) -> Result<Digest, ()> {
+    if !check_params(outlen, key) {
+        return Err(());
+    }
+    let (k, klen) = key.map(|k| (k.as_ptr(), k.len()))
+                       .unwrap_or((ptr::null(), 0));
+    let mut d = Digest { len: outlen, data: [0; HASHBYTES_MAX] };
+    let ret = unsafe {
+        ffi::crypto_generichash_blake2b(d.data.as_mut_ptr(),
+                                        outlen as size_t,
+                                        m.as_ptr(),
+                                        m.len() as c_ulonglong,
+                                        k,
+                                        klen as size_t)
+    };
+    if ret == 0 {
+        Ok(d)
+    } else {
+        Err(())
+    }
+}
+
+/// `hash_salt_personal()` works like `hash()`, but additionally takes a
+/// `Salt` and a `Personal` string.
+///
+/// Different salts or personalization strings yield unrelated digests of
+/// the same message, which allows a single key to be used for different
+/// purposes without having to derive subkeys.
+///
+/// The function returns `Err(())` if `outlen` or the length of `key`
+/// are out of range.
+pub fn hash_salt_personal(m: &[u8],
+                          outlen: usize,
+                          key: Option<&[u8]>,
+                          &Salt(ref salt): &Salt,
+                          &Personal(ref personal): &Personal)
+                          -> Result<Digest, ()> {
+    if !check_params(outlen, key) {
+        return Err(());
+    }
+    let (k, klen) = key.map(|k| (k.as_ptr(), k.len()))
+                       .unwrap_or((ptr::null(), 0));
+    let mut d = Digest { len: outlen, data: [0; HASHBYTES_MAX] };
+    let ret = unsafe {
+        ffi::crypto_generichash_blake2b_salt_personal(d.data.as_mut_ptr(),
+                                                      outlen as size_t,
+                                                      m.as_ptr(),
+                                                      m.len() as c_ulonglong,
+                                                      k,
+                                                      klen as size_t,
+                                                      salt,
+                                                      personal)
+    };
+    if ret == 0 {
+        Ok(d)
+    } else {
+        Err(())
+    }
+}
+
+/// `State` for incremental (streaming) hashing
+///
+/// A `State` allows hashing messages that are too large to be kept in
+/// memory, or that arrive in pieces. Feed the message to `update()`, in as
+/// many calls as needed, and call `finalize()` to get the `Digest`.
+/// The result is the same as calling `hash()` on the concatenation of all
+/// the pieces.
+///
+/// `State` implements `std::io::Write`, so it can be used with for example
+/// `std::io::copy()`.
+///
+/// When a `State` goes out of scope its contents, which may include the
+/// key, will be zeroed out
+pub struct State {
+    outlen: usize,
+    st: ffi::crypto_generichash_blake2b_state,
+}
+
+impl State {
+    /// `new()` creates a `State` that will produce an `outlen`-byte
+    /// `Digest`, optionally under a secret key `key`.
+    ///
+    /// The same restrictions as for `hash()` apply to `outlen` and `key`.
+    /// The function returns `Err(())` if they are out of range.
+    pub fn new(outlen: usize, key: Option<&[u8]>) -> Result<State, ()> {
+        if !check_params(outlen, key) {
+            return Err(());
+        }
+        let (k, klen) = key.map(|k| (k.as_ptr(), k.len()))
+                           .unwrap_or((ptr::null(), 0));
+        let mut state = State {
+            outlen: outlen,
+            st: unsafe { mem::zeroed() },
+        };
+        let ret = unsafe {
+            ffi::crypto_generichash_blake2b_init(&mut state.st,
+                                                 k,
+                                                 klen as size_t,
+                                                 outlen as size_t)
+        };
+        if ret == 0 {
+            Ok(state)
+        } else {
+            Err(())
+        }
+    }
+
+    /// `update()` hashes the next piece of the message `m`
+    pub fn update(&mut self, m: &[u8]) {
+        unsafe {
+            ffi::crypto_generichash_blake2b_update(&mut self.st,
+                                                   m.as_ptr(),
+                                                   m.len() as c_ulonglong);
+        }
+    }
+
+    /// `finalize()` completes the hash computation and returns the `Digest`
+    pub fn finalize(mut self) -> Digest {
+        let mut d = Digest { len: self.outlen, data: [0; HASHBYTES_MAX] };
+        unsafe {
+            ffi::crypto_generichash_blake2b_final(&mut self.st,
+                                                  d.data.as_mut_ptr(),
+                                                  self.outlen as size_t);
+        }
+        d
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        unsafe {
+            let p = &mut self.st as *mut ffi::crypto_generichash_blake2b_state;
+            ffi::sodium_memzero(p as *mut u8,
+                                mem::size_of::<ffi::crypto_generichash_blake2b_state>() as size_t);
+        }
+    }
+}
+
+impl io::Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_vector_1() {
+        // hash of empty string
+        let x = [];
+        let h_expected = [0x78, 0x6a, 0x02, 0xf7, 0x42, 0x01, 0x59, 0x03,
+                          0xc6, 0xc6, 0xfd, 0x85, 0x25, 0x52, 0xd2, 0x72,
+                          0x91, 0x2f, 0x47, 0x40, 0xe1, 0x58, 0x47, 0x61,
+                          0x8a, 0x86, 0xe2, 0x17, 0xf7, 0x1f, 0x54, 0x19,
+                          0xd2, 0x5e, 0x10, 0x31, 0xaf, 0xee, 0x58, 0x53,
+                          0x13, 0x89, 0x64, 0x44, 0x93, 0x4e, 0xb0, 0x4b,
+                          0x90, 0x3a, 0x68, 0x5b, 0x14, 0x48, 0xb7, 0x55,
+                          0xd5, 0x6f, 0x70, 0x1a, 0xfe, 0x9b, 0xe2, 0xce];
+        let h = hash(&x, HASHBYTES_MAX, None).unwrap();
+        assert!(h[..] == h_expected[..]);
+    }
+
+    #[test]
+    fn test_vector_2() {
+        // "abc" hashed to the default digest length
+        let x = [0x61, 0x62, 0x63];
+        let h_expected = [0xbd, 0xdd, 0x81, 0x3c, 0x63, 0x42, 0x39, 0x72,
+                          0x31, 0x71, 0xef, 0x3f, 0xee, 0x98, 0x57, 0x9b,
+                          0x94, 0x96, 0x4e, 0x3b, 0xb1, 0xcb, 0x3e, 0x42,
+                          0x72, 0x62, 0xc8, 0xc0, 0x68, 0xd5, 0x23, 0x19];
+        let h = hash(&x, HASHBYTES, None).unwrap();
+        assert!(h.len() == HASHBYTES);
+        assert!(h[..] == h_expected[..]);
+    }
+
+    #[test]
+    fn test_vector_salt_personal() {
+        let x: Vec<u8> = (0..64).collect();
+        let k: Vec<u8> = (0..32).collect();
+        let salt = Salt(*b"5b6b41ed9b343fe0");
+        let personal = Personal(*b"5126fb2a37400d2a");
+        let h_expected = [0x19, 0x71, 0xc6, 0x30, 0x8e, 0x1e, 0x09, 0x2c,
+                          0xc4, 0xb0, 0x9a, 0x49, 0x86, 0xd7, 0x33, 0xe8,
+                          0xc8, 0x7c, 0x8d, 0x2b, 0x19, 0x6f, 0x0b, 0xac,
+                          0xb0, 0x7e, 0x70, 0x91, 0x76, 0xde, 0xe2, 0x7b,
+                          0x4f, 0x75, 0xd2, 0x7c, 0x68, 0xb1, 0x8c, 0x92,
+                          0x2f, 0x95, 0xee, 0x5d, 0x31, 0x86, 0xda, 0x9e,
+                          0x52, 0x27, 0x09, 0x5c, 0xc5, 0x15, 0x44, 0x17,
+                          0x54, 0x9d, 0xbd, 0x8c, 0xf6, 0x75, 0x9f, 0x94];
+        let h = hash_salt_personal(&x, HASHBYTES_MAX, Some(&k),
+                                   &salt, &personal).unwrap();
+        assert!(h[..] == h_expected[..]);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let m = [0; 64];
+        let salt = Salt([0; SALTBYTES]);
+        let personal = Personal([0; PERSONALBYTES]);
+        for &outlen in [0, HASHBYTES_MIN - 1, HASHBYTES_MAX + 1].iter() {
+            assert!(hash(&m, outlen, None).is_err());
+            assert!(hash_salt_personal(&m, outlen, None,
+                                       &salt, &personal).is_err());
+        }
+        for &keylen in [0, KEYBYTES_MIN - 1, KEYBYTES_MAX + 1].iter() {
+            let k = [0; KEYBYTES_MAX + 1];
+            assert!(hash(&m, HASHBYTES, Some(&k[..keylen])).is_err());
+            assert!(hash_salt_personal(&m, HASHBYTES, Some(&k[..keylen]),
+                                       &salt, &personal).is_err());
+        }
+    }
+
+    #[test]
+    fn test_digest_from_slice() {
+        assert!(Digest::from_slice(&[0; HASHBYTES_MIN - 1]).is_none());
+        assert!(Digest::from_slice(&[0; HASHBYTES_MAX + 1]).is_none());
+        let h = hash(&[], HASHBYTES_MIN, None).unwrap();
+        assert!(Digest::from_slice(&h[..]).unwrap() == h);
+    }
+
+    #[test]
+    fn test_state_invalid_params() {
+        assert!(State::new(HASHBYTES_MIN - 1, None).is_err());
+        assert!(State::new(HASHBYTES_MAX + 1, None).is_err());
+        let k = [0; KEYBYTES_MAX + 1];
+        assert!(State::new(HASHBYTES, Some(&k[..KEYBYTES_MIN - 1])).is_err());
+        assert!(State::new(HASHBYTES, Some(&k[..])).is_err());
+    }
+
+    #[test]
+    fn test_state_streaming() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = randombytes(KEYBYTES);
+            let m = randombytes(i);
+            let mut state = State::new(HASHBYTES_MAX, Some(&k)).unwrap();
+            for chunk in m.chunks(i / 3 + 1) {
+                state.update(chunk);
+            }
+            let d = state.finalize();
+            assert!(d == hash(&m, HASHBYTES_MAX, Some(&k)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_state_io_copy() {
+        use randombytes::randombytes;
+        use std::io;
+        let m = randombytes(100000);
+        let mut state = State::new(HASHBYTES, None).unwrap();
+        io::copy(&mut &m[..], &mut state).unwrap();
+        let d = state.finalize();
+        assert!(d == hash(&m, HASHBYTES, None).unwrap());
+    }
+
+    #[test]
+    fn test_vectors_kat() {
+        // keyed BLAKE2b test vectors from the reference implementation
+        // at https://github.com/BLAKE2/BLAKE2
+        use rustc_serialize::hex::{FromHex};
+        use std::fs::File;
+        use std::io::{BufRead, BufReader};
+
+        let mut r = BufReader::new(File::open("testvectors/blake2b-kat.txt").unwrap());
+        let mut line = String::new();
+        loop {
+            line.clear();
+            r.read_line(&mut line).unwrap();
+            if line.len() == 0 {
+                break;
+            }
+            if line.starts_with("in:") {
+                let msg = line[3..].trim().from_hex().unwrap();
+                line.clear();
+                r.read_line(&mut line).unwrap();
+                let key = line[4..].trim().from_hex().unwrap();
+                line.clear();
+                r.read_line(&mut line).unwrap();
+                let md = line[5..].trim().from_hex().unwrap();
+                let digest = hash(&msg, md.len(), Some(&key)).unwrap();
+                assert!(&digest[..] == &md[..]);
+                let mut state = State::new(md.len(), Some(&key)).unwrap();
+                for chunk in msg.chunks(7) {
+                    state.update(chunk);
+                }
+                let digest = state.finalize();
+                assert!(&digest[..] == &md[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialisation() {
+        use randombytes::randombytes;
+        for i in (HASHBYTES_MIN..HASHBYTES_MAX + 1) {
+            let m = randombytes(i);
+            let d = hash(&m[..], i, None).unwrap();
+            round_trip(d);
+        }
+    }
+}
+
+#[cfg(feature = "benchmarks")]
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_hash(b: &mut test::Bencher) {
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                hash(&m, HASHBYTES, None).unwrap();
+            }
+        });
+    }
+}
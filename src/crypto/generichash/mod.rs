@@ -0,0 +1,47 @@
+//! Generic hashing
+//!
+//! # Security model
+//! The `hash()` function computes a fixed-length fingerprint for an
+//! arbitrary long message. It can be used for file integrity checking and
+//! for creating unique identifiers to index arbitrary long data, and,
+//! optionally keyed, as a message authentication code.
+//!
+//! Unlike the functions in `crypto::hash`, the length of the digest can be
+//! chosen between `HASHBYTES_MIN` and `HASHBYTES_MAX` bytes. The default,
+//! `HASHBYTES`, is a good choice for most applications: it is large enough
+//! to make finding collisions impractical.
+//!
+//! # Selected primitive
+//! `hash()` is currently an implementation of `BLAKE2b`.
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::generichash;
+//!
+//! let data_to_hash = b"some data";
+//! let digest = generichash::hash(data_to_hash, generichash::HASHBYTES,
+//!                                None).unwrap();
+//! ```
+//!
+//! # Example (keyed hashing)
+//! ```
+//! use sodiumoxide::crypto::generichash;
+//!
+//! let key = b"a secret key of at least 16 bytes";
+//! let data_to_hash = b"some data";
+//! let digest = generichash::hash(data_to_hash, generichash::HASHBYTES_MAX,
+//!                                Some(key)).unwrap();
+//! ```
+//!
+//! # Example (streaming)
+//! ```
+//! use sodiumoxide::crypto::generichash;
+//!
+//! let mut state = generichash::State::new(generichash::HASHBYTES,
+//!                                         None).unwrap();
+//! state.update(b"some data");
+//! state.update(b"some more data");
+//! let digest = state.finalize();
+//! ```
+pub use self::blake2b::*;
+pub mod blake2b;
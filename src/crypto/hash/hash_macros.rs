@@ -1,6 +1,13 @@
-macro_rules! hash_module (($hash_name:ident, $hashbytes:expr, $blockbytes:expr) => (
+macro_rules! hash_module (($hash_name:ident,
+                           $hash_state:ident,
+                           $hash_init:ident,
+                           $hash_update:ident,
+                           $hash_final:ident,
+                           $hashbytes:expr,
+                           $blockbytes:expr) => (
 
 use libc::c_ulonglong;
+use std::{io, mem};
 use rustc_serialize;
 
 pub const HASHBYTES: usize = $hashbytes;
@@ -22,6 +29,111 @@ pub fn hash(m: &[u8]) -> Digest {
     }
 }
 
+/// `State` for incremental (streaming) hashing
+///
+/// A `State` allows hashing messages that are too large to be kept in
+/// memory, or that arrive in pieces. Feed the message to `update()`, in as
+/// many calls as needed, and call `finalize()` to get the `Digest`.
+/// The result is the same as calling `hash()` on the concatenation of all
+/// the pieces.
+///
+/// A `State` can be cloned in order to compute the digests of several
+/// messages that share a common prefix.
+///
+/// `State` implements `std::io::Write`, so it can be used with for example
+/// `std::io::copy()`.
+#[derive(Clone)]
+pub struct State($hash_state);
+
+impl State {
+    /// `new()` creates a `State` for hashing a new message
+    pub fn new() -> State {
+        unsafe {
+            let mut st: $hash_state = mem::zeroed();
+            $hash_init(&mut st);
+            State(st)
+        }
+    }
+
+    /// `update()` hashes the next piece of the message `m`
+    pub fn update(&mut self, m: &[u8]) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            $hash_update(st, m.as_ptr(), m.len() as c_ulonglong);
+        }
+    }
+
+    /// `finalize()` completes the hash computation and returns the `Digest`
+    pub fn finalize(self) -> Digest {
+        let State(mut st) = self;
+        let mut h = [0; HASHBYTES];
+        unsafe {
+            $hash_final(&mut st, &mut h);
+        }
+        Digest(h)
+    }
+}
+
+impl io::Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_state {
+    use super::*;
+
+    #[test]
+    fn test_state_streaming() {
+        use randombytes::randombytes;
+        for i in (0..1024usize) {
+            let m = randombytes(i);
+            let mut state = State::new();
+            for chunk in m.chunks(i / 5 + 1) {
+                state.update(chunk);
+            }
+            assert!(state.finalize() == hash(&m));
+        }
+    }
+
+    #[test]
+    fn test_state_clone() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let prefix = randombytes(i);
+            let m1 = randombytes(i);
+            let m2 = randombytes(i);
+            let mut state1 = State::new();
+            state1.update(&prefix);
+            let mut state2 = state1.clone();
+            state1.update(&m1);
+            state2.update(&m2);
+            let mut full1 = prefix.clone();
+            full1.extend(m1.iter().cloned());
+            let mut full2 = prefix.clone();
+            full2.extend(m2.iter().cloned());
+            assert!(state1.finalize() == hash(&full1));
+            assert!(state2.finalize() == hash(&full2));
+        }
+    }
+
+    #[test]
+    fn test_state_io_copy() {
+        use randombytes::randombytes;
+        use std::io;
+        let m = randombytes(100000);
+        let mut state = State::new();
+        io::copy(&mut &m[..], &mut state).unwrap();
+        assert!(state.finalize() == hash(&m));
+    }
+}
+
 #[cfg(test)]
 mod test_encode {
     use super::*;
@@ -36,6 +36,16 @@
 //! let data_to_hash = b"some data";
 //! let digest = hash::hash(data_to_hash);
 //! ```
+//!
+//! # Example (streaming)
+//! ```
+//! use sodiumoxide::crypto::hash;
+//!
+//! let mut state = hash::State::new();
+//! state.update(b"some data");
+//! state.update(b"some more data");
+//! let digest = state.finalize();
+//! ```
 pub use self::sha512::*;
 #[macro_use]
 mod hash_macros;
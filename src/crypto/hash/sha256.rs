@@ -5,9 +5,18 @@
 //! However, for the moment, there do not appear to be alternatives that
 //! inspire satisfactory levels of confidence. One can hope that NIST's
 //! SHA-3 competition will improve the situation.
-use ffi::{crypto_hash_sha256, crypto_hash_sha256_BYTES};
+use ffi::{crypto_hash_sha256,
+          crypto_hash_sha256_state,
+          crypto_hash_sha256_init,
+          crypto_hash_sha256_update,
+          crypto_hash_sha256_final,
+          crypto_hash_sha256_BYTES};
 
 hash_module!(crypto_hash_sha256,
+             crypto_hash_sha256_state,
+             crypto_hash_sha256_init,
+             crypto_hash_sha256_update,
+             crypto_hash_sha256_final,
              crypto_hash_sha256_BYTES,
              64);
 
@@ -45,6 +54,7 @@ mod test {
     }
 
     fn test_nist_vector(filename: &str) {
+        use randombytes::randombytes;
         use rustc_serialize::hex::{FromHex};
         use std::fs::File;
         use std::io::{BufRead, BufReader};
@@ -69,6 +79,17 @@ mod test {
                 let md = line[5..].from_hex().unwrap();
                 let Digest(digest) = hash(msg);
                 assert!(&digest[..] == &md[..]);
+
+                // split the message at random chunk boundaries
+                let mut state = State::new();
+                let mut rest = msg;
+                while rest.len() > 0 {
+                    let n = randombytes(1)[0] as usize % rest.len() + 1;
+                    state.update(&rest[..n]);
+                    rest = &rest[n..];
+                }
+                let Digest(digest) = state.finalize();
+                assert!(&digest[..] == &md[..]);
             }
         }
     }
@@ -0,0 +1,168 @@
+macro_rules! hkdf_module (($hmac:ident) => (
+
+use ffi;
+use libc::size_t;
+use rustc_serialize;
+use std::cmp;
+use crypto::auth::$hmac::{State, Tag, TAGBYTES};
+
+/// Length of the output of the underlying hash function, in bytes
+pub const HASHBYTES: usize = TAGBYTES;
+/// Maximum number of bytes `expand()` can produce
+pub const OUTPUTBYTES_MAX: usize = 255 * HASHBYTES;
+
+/// Pseudorandom key, the output of `extract()`
+///
+/// When a `Prk` goes out of scope its contents
+/// will be zeroed out
+pub struct Prk(pub [u8; HASHBYTES]);
+
+newtype_drop!(Prk);
+newtype_clone!(Prk);
+newtype_impl!(Prk, HASHBYTES);
+
+/// Output keying material, the output of `expand()`
+///
+/// An `Okm` can be anywhere between 0 and `OUTPUTBYTES_MAX` bytes long. It
+/// implements the traits `PartialEq` and `Eq` using constant-time comparison
+/// functions.
+///
+/// When an `Okm` goes out of scope its contents
+/// will be zeroed out
+pub struct Okm(Vec<u8>);
+
+impl Okm {
+    /// `len()` returns the length of the `Okm` in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Drop for Okm {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sodium_memzero(self.0.as_mut_ptr(), self.0.len() as size_t);
+        }
+    }
+}
+
+impl Clone for Okm {
+    fn clone(&self) -> Okm {
+        Okm(self.0.clone())
+    }
+}
+
+impl PartialEq for Okm {
+    fn eq(&self, other: &Okm) -> bool {
+        use crypto::verify::safe_memcmp;
+        safe_memcmp(&self[..], &other[..])
+    }
+}
+
+impl Eq for Okm {}
+
+newtype_varlen_index!(Okm, x => x.0[..]);
+
+/// `extract()` computes a pseudorandom key from the input keying material
+/// `ikm` and an optional, non-secret `salt`.
+///
+/// If no salt is given, a string of `HASHBYTES` zeros is used, as specified
+/// by RFC 5869.
+pub fn extract(salt: Option<&[u8]>, ikm: &[u8]) -> Prk {
+    let zeros = [0u8; HASHBYTES];
+    let mut state = State::init_from_bytes(salt.unwrap_or(&zeros));
+    state.update(ikm);
+    let Tag(prk) = state.finalize();
+    Prk(prk)
+}
+
+/// `expand()` derives `len` bytes of output keying material from the
+/// pseudorandom key `prk`, bound to the optional context and application
+/// specific information `info`.
+///
+/// `len` can be at most `OUTPUTBYTES_MAX`, otherwise `expand()` returns
+/// `Err(())`.
+pub fn expand(&Prk(ref prk): &Prk, info: &[u8], len: usize) -> Result<Okm, ()> {
+    if len > OUTPUTBYTES_MAX {
+        return Err(());
+    }
+    // allocated once, so that no copy of the output is left behind by a
+    // reallocation
+    let mut okm = Vec::with_capacity(len);
+    let mut t = [0u8; HASHBYTES];
+    let mut i = 1u8;
+    while okm.len() < len {
+        let mut state = State::init_from_bytes(prk);
+        if i > 1 {
+            state.update(&t);
+        }
+        state.update(info);
+        state.update(&[i]);
+        let Tag(ti) = state.finalize();
+        t = ti;
+        let n = cmp::min(HASHBYTES, len - okm.len());
+        okm.extend(t[..n].iter().cloned());
+        i = i.wrapping_add(1);
+    }
+    unsafe {
+        ffi::sodium_memzero(t.as_mut_ptr(), t.len() as size_t);
+    }
+    Ok(Okm(okm))
+}
+
+#[cfg(test)]
+mod test_m {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_expand_lengths() {
+        let prk = extract(Some(b"salt"), b"input keying material");
+        let okm_max = expand(&prk, b"info", OUTPUTBYTES_MAX).unwrap();
+        assert!(okm_max.len() == OUTPUTBYTES_MAX);
+        for len in (0..3 * HASHBYTES) {
+            let okm = expand(&prk, b"info", len).unwrap();
+            assert!(okm.len() == len);
+            assert!(&okm[..] == &okm_max[..len]);
+        }
+    }
+
+    #[test]
+    fn test_okm_eq() {
+        let prk = extract(None, b"input keying material");
+        let okm = expand(&prk, b"info", 2 * HASHBYTES).unwrap();
+        assert!(okm == okm.clone());
+        assert!(okm != expand(&prk, b"info", 2 * HASHBYTES - 1).unwrap());
+        assert!(okm != expand(&prk, b"info", 0).unwrap());
+    }
+
+    #[test]
+    fn test_expand_too_long() {
+        let prk = extract(None, b"input keying material");
+        assert!(expand(&prk, b"", OUTPUTBYTES_MAX + 1).is_err());
+    }
+
+    #[test]
+    fn test_extract_no_salt() {
+        let zeros = [0u8; HASHBYTES];
+        let prk1 = extract(None, b"input keying material");
+        let prk2 = extract(Some(&zeros), b"input keying material");
+        assert!(prk1 == prk2);
+    }
+
+    #[test]
+    fn test_expand_info() {
+        let prk = extract(None, b"input keying material");
+        let okm1 = expand(&prk, b"info1", HASHBYTES).unwrap();
+        let okm2 = expand(&prk, b"info2", HASHBYTES).unwrap();
+        assert!(okm1 != okm2);
+    }
+
+    #[test]
+    fn test_serialisation() {
+        let prk = extract(Some(b"salt"), b"input keying material");
+        round_trip(prk);
+    }
+}
+
+));
@@ -0,0 +1,35 @@
+//! HKDF with `HMAC-SHA-512`
+hkdf_module!(hmacsha512);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_1() {
+        // inputs of RFC 5869, A.1 (test case 1), with SHA-512 as hash function
+        let ikm = [0x0bu8; 22];
+        let salt = [0x00,0x01,0x02,0x03,0x04,0x05,0x06,0x07,
+                    0x08,0x09,0x0a,0x0b,0x0c];
+        let info = [0xf0,0xf1,0xf2,0xf3,0xf4,0xf5,0xf6,0xf7,
+                    0xf8,0xf9];
+        let prk_expected = [0x66,0x57,0x99,0x82,0x37,0x37,0xde,0xd0,
+                            0x4a,0x88,0xe4,0x7e,0x54,0xa5,0x89,0x0b,
+                            0xb2,0xc3,0xd2,0x47,0xc7,0xa4,0x25,0x4a,
+                            0x8e,0x61,0x35,0x07,0x23,0x59,0x0a,0x26,
+                            0xc3,0x62,0x38,0x12,0x7d,0x86,0x61,0xb8,
+                            0x8c,0xf8,0x0e,0xf8,0x02,0xd5,0x7e,0x2f,
+                            0x7c,0xeb,0xcf,0x1e,0x00,0xe0,0x83,0x84,
+                            0x8b,0xe1,0x99,0x29,0xc6,0x1b,0x42,0x37];
+        let okm_expected = [0x83,0x23,0x90,0x08,0x6c,0xda,0x71,0xfb,
+                            0x47,0x62,0x5b,0xb5,0xce,0xb1,0x68,0xe4,
+                            0xc8,0xe2,0x6a,0x1a,0x16,0xed,0x34,0xd9,
+                            0xfc,0x7f,0xe9,0x2c,0x14,0x81,0x57,0x93,
+                            0x38,0xda,0x36,0x2c,0xb8,0xd9,0xf9,0x25,
+                            0xd7,0xcb];
+        let prk = extract(Some(&salt), &ikm);
+        assert!(prk == Prk(prk_expected));
+        let okm = expand(&prk, &info, 42).unwrap();
+        assert!(&okm[..] == &okm_expected[..]);
+    }
+}
@@ -0,0 +1,44 @@
+//! HMAC-based key derivation (HKDF)
+//!
+//! HKDF, specified in [RFC 5869](https://tools.ietf.org/html/rfc5869),
+//! turns some initial keying material, such as the output of a Diffie-Hellman
+//! exchange, into one or more cryptographically strong secret keys. It is
+//! made of two steps:
+//!
+//! - `extract()` concentrates the entropy of the input keying material and an
+//!   optional salt in a pseudorandom key (`Prk`);
+//! - `expand()` derives up to `OUTPUTBYTES_MAX` bytes of output keying
+//!   material (`Okm`) from that pseudorandom key and an application specific
+//!   `info` string.
+//!
+//! When the input keying material is already a uniformly random key,
+//! `crypto::kdf` is simpler to use. HKDF is mostly useful to interoperate with
+//! protocols that specify it.
+//!
+//! # Selected primitive
+//! `crypto::hkdf` uses `HMAC-SHA-256`, as implemented by
+//! `crypto::auth::hmacsha256`.
+//!
+//! # Alternate primitives
+//!
+//! -----------------------------------------------------
+//! |hkdf       |HMAC           |HASHBYTES|OUTPUTBYTES_MAX|
+//! |-----------|---------------|---------|---------------|
+//! |hmacsha256 |HMAC-SHA-256   |32       |8160           |
+//! |hmacsha512 |HMAC-SHA-512   |64       |16320          |
+//! -----------------------------------------------------
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::hkdf;
+//!
+//! let prk = hkdf::extract(Some(b"salt"), b"input keying material");
+//! let encryption_key = hkdf::expand(&prk, b"encryption", 32).unwrap();
+//! let mac_key = hkdf::expand(&prk, b"authentication", 32).unwrap();
+//! assert!(encryption_key != mac_key);
+//! ```
+pub use self::hmacsha256::*;
+#[macro_use]
+mod hkdf_macros;
+pub mod hmacsha256;
+pub mod hmacsha512;
@@ -0,0 +1,206 @@
+//! `blake2b` derives subkeys with `BLAKE2b`, a cryptographic hash function
+//! specified in [RFC 7693](https://tools.ietf.org/html/rfc7693).
+use ffi;
+use libc::size_t;
+use randombytes::randombytes_into;
+use rustc_serialize;
+
+pub const BYTES_MIN: usize = ffi::crypto_kdf_blake2b_BYTES_MIN;
+pub const BYTES_MAX: usize = ffi::crypto_kdf_blake2b_BYTES_MAX;
+pub const CONTEXTBYTES: usize = ffi::crypto_kdf_blake2b_CONTEXTBYTES;
+pub const KEYBYTES: usize = ffi::crypto_kdf_blake2b_KEYBYTES;
+
+/// Master `Key` from which subkeys are derived
+///
+/// When a `Key` goes out of scope its contents
+/// will be zeroed out
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/// `Context` in which subkeys are derived
+#[derive(Copy)]
+pub struct Context(pub [u8; CONTEXTBYTES]);
+
+newtype_clone!(Context);
+newtype_impl!(Context, CONTEXTBYTES);
+
+/// Subkey-structure
+///
+/// A `Subkey` can be anywhere between `BYTES_MIN` and `BYTES_MAX`
+/// bytes long. It implements the traits `PartialEq` and `Eq` using
+/// constant-time comparison functions.
+///
+/// When a `Subkey` goes out of scope its contents
+/// will be zeroed out
+pub struct Subkey {
+    len: usize,
+    data: [u8; BYTES_MAX],
+}
+
+impl Subkey {
+    /// `len()` returns the length of the `Subkey` in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Drop for Subkey {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sodium_memzero(self.data.as_mut_ptr(), self.data.len() as size_t);
+        }
+    }
+}
+
+impl Clone for Subkey {
+    fn clone(&self) -> Subkey {
+        Subkey { len: self.len, data: self.data }
+    }
+}
+
+impl PartialEq for Subkey {
+    fn eq(&self, other: &Subkey) -> bool {
+        use crypto::verify::safe_memcmp;
+        safe_memcmp(&self[..], &other[..])
+    }
+}
+
+impl Eq for Subkey {}
+
+newtype_varlen_index!(Subkey, x => x.data[..x.len]);
+
+/// `gen_key()` randomly generates a master key
+///
+/// THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn gen_key() -> Key {
+    let mut k = [0; KEYBYTES];
+    randombytes_into(&mut k);
+    Key(k)
+}
+
+/// `derive_from_key()` derives the subkey with identifier `subkey_id` and
+/// length `subkey_len` from the master key `k`, in the context `ctx`.
+///
+/// `subkey_len` must be between `BYTES_MIN` and `BYTES_MAX`, otherwise
+/// `derive_from_key()` returns `Err(())`.
+pub fn derive_from_key(subkey_len: usize,
+                       subkey_id: u64,
+                       &Context(ref ctx): &Context,
+                       &Key(ref k): &Key) -> Result<Subkey, ()> {
+    if subkey_len < BYTES_MIN || subkey_len > BYTES_MAX {
+        return Err(());
+    }
+    let mut subkey = Subkey { len: subkey_len, data: [0; BYTES_MAX] };
+    let ret = unsafe {
+        ffi::crypto_kdf_blake2b_derive_from_key(subkey.data.as_mut_ptr(),
+                                                subkey_len as size_t,
+                                                subkey_id,
+                                                ctx,
+                                                k)
+    };
+    if ret == 0 {
+        Ok(subkey)
+    } else {
+        Err(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_derive_from_key() {
+        let k = gen_key();
+        let ctx = Context(*b"__auth__");
+        for len in (BYTES_MIN..BYTES_MAX + 1) {
+            let s1 = derive_from_key(len, 1, &ctx, &k).unwrap();
+            let s2 = derive_from_key(len, 1, &ctx, &k).unwrap();
+            let s3 = derive_from_key(len, 2, &ctx, &k).unwrap();
+            assert!(s1.len() == len);
+            assert!(s1 == s2);
+            assert!(s1 != s3);
+        }
+    }
+
+    #[test]
+    fn test_derive_from_key_context() {
+        let k = gen_key();
+        let s1 = derive_from_key(32, 1, &Context(*b"context1"), &k).unwrap();
+        let s2 = derive_from_key(32, 1, &Context(*b"context2"), &k).unwrap();
+        assert!(s1 != s2);
+    }
+
+    #[test]
+    fn test_derive_from_key_invalid_len() {
+        let k = gen_key();
+        let ctx = Context(*b"__auth__");
+        for len in (0..BYTES_MIN) {
+            assert!(derive_from_key(len, 1, &ctx, &k).is_err());
+        }
+        assert!(derive_from_key(BYTES_MAX + 1, 1, &ctx, &k).is_err());
+        assert!(derive_from_key(1 << 20, 1, &ctx, &k).is_err());
+    }
+
+    #[test]
+    fn test_vector_1() {
+        // corresponding to test/default/kdf.c from libsodium
+        let mut key = [0u8; KEYBYTES];
+        for i in (0..KEYBYTES) {
+            key[i] = i as u8;
+        }
+        let key = Key(key);
+        let ctx = Context(*b"KDF test");
+        let expected_0 = [0xa0,0xc7,0x24,0x40,0x47,0x28,0xc8,0xbb,
+                          0x95,0xe5,0x43,0x3e,0xb6,0xa9,0x71,0x61,
+                          0x71,0x14,0x4d,0x61,0xef,0xb2,0x3e,0x74,
+                          0xb8,0x73,0xfc,0xbe,0xda,0x51,0xd8,0x07,
+                          0x1b,0x5d,0x70,0xaa,0xe1,0x20,0x66,0xdf,
+                          0xc9,0x4c,0xe9,0x43,0xf1,0x45,0xaa,0x17,
+                          0x6c,0x05,0x50,0x40,0xc3,0xdd,0x73,0xb0,
+                          0xa1,0x5e,0x36,0x25,0x4d,0x45,0x06,0x14];
+        let expected_1 = [0x02,0x50,0x7f,0x14,0x4f,0xa9,0xbf,0x19,
+                          0x01,0x0b,0xf7,0xc7,0x0b,0x23,0x5b,0x4c,
+                          0x26,0x63,0xcc,0x00,0xe0,0x74,0xf9,0x29,
+                          0x60,0x2a,0x5e,0x2c,0x10,0xa7,0x80,0x75,
+                          0x7d,0x2a,0x39,0x93,0xd0,0x6d,0xeb,0xc3,
+                          0x78,0xa9,0x0e,0xfd,0xac,0x19,0x6d,0xd8,
+                          0x41,0x81,0x7b,0x97,0x7d,0x67,0xb7,0x86,
+                          0x80,0x4f,0x6d,0x3c,0xd5,0x85,0xba,0xb5];
+        let subkey_0 = derive_from_key(BYTES_MAX, 0, &ctx, &key).unwrap();
+        let subkey_1 = derive_from_key(BYTES_MAX, 1, &ctx, &key).unwrap();
+        assert!(&subkey_0[..] == &expected_0[..]);
+        assert!(&subkey_1[..] == &expected_1[..]);
+
+        let expected_16 = [0xa5,0x29,0x21,0x66,0x24,0xef,0x91,0x61,
+                           0xe4,0xcf,0x11,0x72,0x72,0xaa,0xff,0xf2];
+        let expected_64 = [0x06,0xae,0x14,0x30,0x8e,0xee,0xda,0x62,
+                           0xa0,0x0c,0xb6,0xd5,0xed,0xf1,0x8d,0x17,
+                           0x07,0x02,0x95,0x15,0xdb,0x98,0xf4,0x72,
+                           0xbb,0xf0,0x61,0x74,0x19,0x30,0x1b,0x1d,
+                           0x4f,0x4f,0x2a,0xb6,0x58,0x49,0x44,0x6b,
+                           0xe4,0x6f,0x87,0xe1,0xd3,0x1c,0x6c,0x74,
+                           0x28,0x38,0x97,0xb9,0x97,0x6f,0x70,0xd8,
+                           0xa1,0x62,0x53,0xac,0x92,0x7e,0x0d,0x9f];
+        let subkey_16 = derive_from_key(16, 16, &ctx, &key).unwrap();
+        let subkey_64 = derive_from_key(64, 64, &ctx, &key).unwrap();
+        assert!(subkey_16.len() == 16);
+        assert!(&subkey_16[..] == &expected_16[..]);
+        assert!(&subkey_64[..] == &expected_64[..]);
+    }
+
+    #[test]
+    fn test_serialisation() {
+        for _ in (0..256usize) {
+            let k = gen_key();
+            round_trip(k);
+            round_trip(Context(*b"__auth__"));
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Key derivation
+//!
+//! Multiple secret subkeys can be derived from a single master key.
+//!
+//! Given the master key and a key identifier, a subkey can be deterministically
+//! computed. However, given a subkey, an attacker cannot compute the master key
+//! nor any other subkeys.
+//!
+//! Every subkey is derived from the master key, a 64 bit subkey identifier and
+//! a `Context`. The context is an 8 byte string that describes what the
+//! subkeys are used for, such as `b"UserName"` or `b"__auth__"`, so that the
+//! same identifier used in different parts of an application yields unrelated
+//! keys. It doesn't have to be secret.
+//!
+//! # Selected primitive
+//! `crypto::kdf` is `blake2b`: a subkey is the keyed BLAKE2b hash of an empty
+//! message, with the subkey identifier as salt and the context as
+//! personalization.
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::kdf;
+//!
+//! let master_key = kdf::gen_key();
+//! let context = kdf::Context(*b"Examples");
+//! let subkey1 = kdf::derive_from_key(32, 1, &context, &master_key).unwrap();
+//! let subkey2 = kdf::derive_from_key(32, 2, &context, &master_key).unwrap();
+//! assert!(subkey1 != subkey2);
+//! ```
+pub use self::blake2b::*;
+pub mod blake2b;
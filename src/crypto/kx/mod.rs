@@ -0,0 +1,33 @@
+//! Key exchange
+//!
+//! Using the key exchange API, two parties can securely compute a set of
+//! shared keys using their peer's public key and their own secret key.
+//!
+//! Each party gets two session keys: `rx`, used to decrypt data received
+//! from the peer, and `tx`, used to encrypt data sent to the peer. The
+//! client's `tx` is the server's `rx` and vice versa, so the two directions
+//! never share a key.
+//!
+//! # Selected primitive
+//! `crypto::kx` is `x25519blake2b`: the session keys are computed as
+//! `BLAKE2b-512(q || client_pk || server_pk)`, where `q` is the X25519 shared
+//! point, and the resulting 64 bytes are split in two keys.
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::kx;
+//!
+//! let (client_pk, client_sk) = kx::gen_keypair();
+//! let (server_pk, server_sk) = kx::gen_keypair();
+//!
+//! // client_pk and server_pk are exchanged over the network
+//! let (client_rx, client_tx) = kx::client_session_keys(&client_pk, &client_sk,
+//!                                                      &server_pk).unwrap();
+//! let (server_rx, server_tx) = kx::server_session_keys(&server_pk, &server_sk,
+//!                                                      &client_pk).unwrap();
+//!
+//! assert!(client_rx == server_tx);
+//! assert!(client_tx == server_rx);
+//! ```
+pub use self::x25519blake2b::*;
+pub mod x25519blake2b;
@@ -0,0 +1,235 @@
+//! `x25519blake2b` is the key exchange construction used by libsodium's
+//! `crypto_kx`: an X25519 scalar multiplication followed by BLAKE2b-512 of
+//! the shared point and both public keys.
+use ffi;
+use rustc_serialize;
+
+pub const PUBLICKEYBYTES: usize = ffi::crypto_kx_PUBLICKEYBYTES;
+pub const SECRETKEYBYTES: usize = ffi::crypto_kx_SECRETKEYBYTES;
+pub const SEEDBYTES: usize = ffi::crypto_kx_SEEDBYTES;
+pub const SESSIONKEYBYTES: usize = ffi::crypto_kx_SESSIONKEYBYTES;
+
+/// `PublicKey` for key exchange
+#[derive(Copy)]
+pub struct PublicKey(pub [u8; PUBLICKEYBYTES]);
+
+newtype_clone!(PublicKey);
+newtype_impl!(PublicKey, PUBLICKEYBYTES);
+
+/// `SecretKey` for key exchange
+///
+/// When a `SecretKey` goes out of scope its contents
+/// will be zeroed out
+pub struct SecretKey(pub [u8; SECRETKEYBYTES]);
+
+newtype_drop!(SecretKey);
+newtype_clone!(SecretKey);
+newtype_impl!(SecretKey, SECRETKEYBYTES);
+
+/// `Seed` that can be used for keypair generation
+///
+/// The `Seed` is used by `keypair_from_seed()` to generate
+/// a secret and public key.
+///
+/// When a `Seed` goes out of scope its contents
+/// will be zeroed out
+pub struct Seed(pub [u8; SEEDBYTES]);
+
+newtype_drop!(Seed);
+newtype_clone!(Seed);
+newtype_impl!(Seed, SEEDBYTES);
+
+/// `SessionKey` is returned by `client_session_keys()` and
+/// `server_session_keys()` and is the exchanged secret between the client
+/// and server.
+///
+/// When a `SessionKey` goes out of scope its contents
+/// will be zeroed out
+pub struct SessionKey(pub [u8; SESSIONKEYBYTES]);
+
+newtype_drop!(SessionKey);
+newtype_clone!(SessionKey);
+newtype_impl!(SessionKey, SESSIONKEYBYTES);
+
+/// `gen_keypair()` randomly generates a secret key and a corresponding public
+/// key.
+///
+/// THREAD SAFETY: `gen_keypair()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn gen_keypair() -> (PublicKey, SecretKey) {
+    unsafe {
+        let mut pk = [0u8; PUBLICKEYBYTES];
+        let mut sk = [0u8; SECRETKEYBYTES];
+        ffi::crypto_kx_keypair(&mut pk, &mut sk);
+        (PublicKey(pk), SecretKey(sk))
+    }
+}
+
+/// `keypair_from_seed()` computes a secret key and a corresponding public key
+/// from a `Seed`.
+pub fn keypair_from_seed(&Seed(ref seed): &Seed) -> (PublicKey, SecretKey) {
+    unsafe {
+        let mut pk = [0u8; PUBLICKEYBYTES];
+        let mut sk = [0u8; SECRETKEYBYTES];
+        ffi::crypto_kx_seed_keypair(&mut pk, &mut sk, seed);
+        (PublicKey(pk), SecretKey(sk))
+    }
+}
+
+/// `client_session_keys()` computes a pair of shared keys (`rx` and `tx`) using the
+/// client's public key `client_pk`, the client's secret key `client_sk` and the
+/// server's public key `server_pk`.
+///
+/// `rx` should be used to decrypt data sent by the server and `tx` to encrypt
+/// data sent to the server. The server computes the same keys, with their
+/// roles swapped, using `server_session_keys()`.
+///
+/// If `server_pk` is not acceptable, `client_session_keys()` returns `Err(())`.
+pub fn client_session_keys(&PublicKey(ref client_pk): &PublicKey,
+                           &SecretKey(ref client_sk): &SecretKey,
+                           &PublicKey(ref server_pk): &PublicKey)
+                           -> Result<(SessionKey, SessionKey), ()> {
+    let mut rx = SessionKey([0u8; SESSIONKEYBYTES]);
+    let mut tx = SessionKey([0u8; SESSIONKEYBYTES]);
+    let ret = unsafe {
+        ffi::crypto_kx_client_session_keys(&mut rx.0,
+                                           &mut tx.0,
+                                           client_pk,
+                                           client_sk,
+                                           server_pk)
+    };
+    if ret == 0 {
+        Ok((rx, tx))
+    } else {
+        Err(())
+    }
+}
+
+/// `server_session_keys()` computes a pair of shared keys (`rx` and `tx`) using the
+/// server's public key `server_pk`, the server's secret key `server_sk` and the
+/// client's public key `client_pk`.
+///
+/// `rx` should be used to decrypt data sent by the client and `tx` to encrypt
+/// data sent to the client. The client computes the same keys, with their
+/// roles swapped, using `client_session_keys()`.
+///
+/// If `client_pk` is not acceptable, `server_session_keys()` returns `Err(())`.
+pub fn server_session_keys(&PublicKey(ref server_pk): &PublicKey,
+                           &SecretKey(ref server_sk): &SecretKey,
+                           &PublicKey(ref client_pk): &PublicKey)
+                           -> Result<(SessionKey, SessionKey), ()> {
+    let mut rx = SessionKey([0u8; SESSIONKEYBYTES]);
+    let mut tx = SessionKey([0u8; SESSIONKEYBYTES]);
+    let ret = unsafe {
+        ffi::crypto_kx_server_session_keys(&mut rx.0,
+                                           &mut tx.0,
+                                           server_pk,
+                                           server_sk,
+                                           client_pk)
+    };
+    if ret == 0 {
+        Ok((rx, tx))
+    } else {
+        Err(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_kx() {
+        for _ in (0..256usize) {
+            let (client_pk, client_sk) = gen_keypair();
+            let (server_pk, server_sk) = gen_keypair();
+            let (client_rx, client_tx) =
+                client_session_keys(&client_pk, &client_sk, &server_pk).unwrap();
+            let (server_rx, server_tx) =
+                server_session_keys(&server_pk, &server_sk, &client_pk).unwrap();
+            assert!(client_rx == server_tx);
+            assert!(client_tx == server_rx);
+            assert!(client_rx != client_tx);
+        }
+    }
+
+    #[test]
+    fn test_kx_wrong_key() {
+        let (client_pk, client_sk) = gen_keypair();
+        let (server_pk, server_sk) = gen_keypair();
+        let (other_pk, _) = gen_keypair();
+        let (client_rx, client_tx) =
+            client_session_keys(&client_pk, &client_sk, &server_pk).unwrap();
+        let (server_rx, server_tx) =
+            server_session_keys(&server_pk, &server_sk, &other_pk).unwrap();
+        assert!(client_rx != server_tx);
+        assert!(client_tx != server_rx);
+    }
+
+    #[test]
+    fn test_kx_small_order() {
+        // corresponding to test/default/kx.c from libsodium
+        let small_order_p = PublicKey([0xe0,0xeb,0x7a,0x7c,0x3b,0x41,0xb8,0xae,
+                                       0x16,0x56,0xe3,0xfa,0xf1,0x9f,0xc4,0x6a,
+                                       0xda,0x09,0x8d,0xeb,0x9c,0x32,0xb1,0xfd,
+                                       0x86,0x62,0x05,0x16,0x5f,0x49,0xb8,0x00]);
+        let (pk, sk) = gen_keypair();
+        assert!(client_session_keys(&pk, &sk, &small_order_p).is_err());
+        assert!(server_session_keys(&pk, &sk, &small_order_p).is_err());
+    }
+
+    #[test]
+    fn test_vector_1() {
+        // corresponding to test/default/kx.c from libsodium
+        let mut seed = [0u8; SEEDBYTES];
+        for i in (0..SEEDBYTES) {
+            seed[i] = i as u8;
+        }
+        let (client_pk, client_sk) = keypair_from_seed(&Seed(seed));
+        let client_pk_expected = [0x0e,0x02,0x16,0x22,0x3f,0x14,0x71,0x43,
+                                  0xd3,0x26,0x15,0xa9,0x11,0x89,0xc2,0x88,
+                                  0xc1,0x72,0x8c,0xba,0x3c,0xc5,0xf9,0xf6,
+                                  0x21,0xb1,0x02,0x6e,0x03,0xd8,0x31,0x29];
+        let client_sk_expected = [0xcb,0x2f,0x51,0x60,0xfc,0x1f,0x7e,0x05,
+                                  0xa5,0x5e,0xf4,0x9d,0x34,0x0b,0x48,0xda,
+                                  0x2e,0x5a,0x78,0x09,0x9d,0x53,0x39,0x33,
+                                  0x51,0xcd,0x57,0x9d,0xd4,0x25,0x03,0xd6];
+        assert!(client_pk == PublicKey(client_pk_expected));
+        assert!(client_sk == SecretKey(client_sk_expected));
+
+        // the server seed is the client seed incremented by one
+        seed[0] += 1;
+        let (server_pk, server_sk) = keypair_from_seed(&Seed(seed));
+        let (server_rx, server_tx) =
+            server_session_keys(&server_pk, &server_sk, &client_pk).unwrap();
+        let (client_rx, client_tx) =
+            client_session_keys(&client_pk, &client_sk, &server_pk).unwrap();
+        let server_rx_expected = [0x62,0xc8,0xf4,0xfa,0x81,0x80,0x0a,0xbd,
+                                  0x05,0x77,0xd9,0x99,0x18,0xd1,0x29,0xb6,
+                                  0x5d,0xeb,0x78,0x9a,0xf8,0xc8,0x35,0x1f,
+                                  0x39,0x1f,0xeb,0x0c,0xbf,0x23,0x86,0x04];
+        let server_tx_expected = [0x74,0x95,0x19,0xc6,0x80,0x59,0xbc,0xe6,
+                                  0x9f,0x7c,0xfc,0xc7,0xb3,0x87,0xa3,0xde,
+                                  0x1a,0x1e,0x82,0x37,0xd1,0x10,0x99,0x13,
+                                  0x23,0xbf,0x62,0x87,0x01,0x15,0x73,0x1a];
+        assert!(server_rx == SessionKey(server_rx_expected));
+        assert!(server_tx == SessionKey(server_tx_expected));
+        assert!(client_rx == SessionKey(server_tx_expected));
+        assert!(client_tx == SessionKey(server_rx_expected));
+    }
+
+    #[test]
+    fn test_serialisation() {
+        for _ in (0..256usize) {
+            let (pk, sk) = gen_keypair();
+            let (other_pk, _) = gen_keypair();
+            let (rx, tx) = client_session_keys(&pk, &sk, &other_pk).unwrap();
+            round_trip(pk);
+            round_trip(sk);
+            round_trip(rx);
+            round_trip(tx);
+        }
+    }
+}
@@ -27,6 +27,18 @@
 //! let tag = onetimeauth::authenticate(data_to_authenticate, &key);
 //! assert!(onetimeauth::verify(&tag, data_to_authenticate, &key));
 //! ```
+//!
+//! # Example (streaming)
+//! ```
+//! use sodiumoxide::crypto::onetimeauth;
+//!
+//! let key = onetimeauth::gen_key();
+//! let mut state = onetimeauth::State::init(&key);
+//! state.update(b"some data");
+//! state.update(b"some more data");
+//! let tag = state.finalize();
+//! assert!(onetimeauth::verify(&tag, b"some datasome more data", &key));
+//! ```
 pub use self::poly1305::*;
 #[path = "../auth/auth_macros.rs"]
 #[macro_use]
@@ -0,0 +1,257 @@
+macro_rules! argon2_module (($pwhash_name:ident,
+                             $str_name:ident,
+                             $verify_name:ident,
+                             $needs_rehash_name:ident,
+                             $saltbytes:expr,
+                             $strbytes:expr,
+                             $strprefix:expr,
+                             $algorithm:expr,
+                             $alg:expr,
+                             $opslimit_interactive:expr,
+                             $memlimit_interactive:expr,
+                             $opslimit_moderate:expr,
+                             $memlimit_moderate:expr,
+                             $opslimit_sensitive:expr,
+                             $memlimit_sensitive:expr) => (
+
+use randombytes::randombytes_into;
+use libc::{c_ulonglong, size_t};
+use rustc_serialize;
+
+pub const SALTBYTES: usize = $saltbytes;
+pub const STRBYTES: usize = $strbytes;
+pub const STRPREFIX: &'static str = $strprefix;
+pub const OPSLIMIT_INTERACTIVE: OpsLimit = OpsLimit($opslimit_interactive);
+pub const MEMLIMIT_INTERACTIVE: MemLimit = MemLimit($memlimit_interactive);
+pub const OPSLIMIT_MODERATE: OpsLimit = OpsLimit($opslimit_moderate);
+pub const MEMLIMIT_MODERATE: MemLimit = MemLimit($memlimit_moderate);
+pub const OPSLIMIT_SENSITIVE: OpsLimit = OpsLimit($opslimit_sensitive);
+pub const MEMLIMIT_SENSITIVE: MemLimit = MemLimit($memlimit_sensitive);
+
+/// `OpsLimit` represents the number of passes over the memory that the
+/// functions in this module will perform.
+///
+/// A high `OpsLimit` will make the functions
+/// require more CPU cycles
+#[derive(Copy, Clone)]
+pub struct OpsLimit(pub usize);
+
+/// `MemLimit` represents the amount of RAM that the functions in this
+/// module will use, in bytes.
+///
+/// It is highly recommended to allow the functions to use
+/// at least 16 megabytes.
+#[derive(Copy, Clone)]
+pub struct MemLimit(pub usize);
+
+/// `Salt` used for password hashing
+#[derive(Copy)]
+pub struct Salt(pub [u8; SALTBYTES]);
+newtype_clone!(Salt);
+newtype_impl!(Salt, SALTBYTES);
+
+/// `HashedPassword`is a password verifier generated from a password
+///
+/// A `HashedPassword` is zero-terminated, includes only ASCII characters and can
+/// be conveniently stored into SQL databases and other data stores. No
+/// additional information has to be stored in order to verify the password.
+#[derive(Copy)]
+pub struct HashedPassword(pub [u8; STRBYTES]);
+newtype_clone!(HashedPassword);
+newtype_impl!(HashedPassword, STRBYTES);
+hashed_password_str_impl!(STRBYTES, $algorithm);
+
+/// `gen_salt()` randombly generates a new `Salt` for key derivation
+///
+/// THREAD SAFETY: `gen_salt()` is thread-safe provided that you have called
+/// `sodiumoxide::init()` once before using any other function from sodiumoxide.
+pub fn gen_salt() -> Salt {
+    let mut salt = Salt([0; SALTBYTES]);
+    {
+        let Salt(ref mut sb) = salt;
+        randombytes_into(sb);
+    }
+    salt
+}
+
+/// The `derive_key()` function derives a key from a password and a `Salt`
+///
+/// The computed key is stored into out. It must be at least 16 bytes long.
+///
+/// `opslimit` represents a maximum amount of computations to perform. Raising
+/// this number will make the function require more CPU cycles to compute a key.
+///
+/// `memlimit` is the maximum amount of RAM that the function will use, in
+/// bytes.
+///
+/// For interactive, online operations, `OPSLIMIT_INTERACTIVE` and
+/// `MEMLIMIT_INTERACTIVE` provide a safe base line for these two
+/// parameters. However, using higher values may improve security.
+///
+/// `OPSLIMIT_MODERATE` and `MEMLIMIT_MODERATE` are a compromise between
+/// the interactive and the sensitive presets.
+///
+/// For highly sensitive data, `OPSLIMIT_SENSITIVE` and `MEMLIMIT_SENSITIVE` can
+/// be used as an alternative. But with these parameters, deriving a key takes
+/// several seconds and requires up to 1 gigabyte of dedicated RAM.
+///
+/// The salt should be unpredictable. `gen_salt()` is the easiest way to create a `Salt`.
+///
+/// Keep in mind that in order to produce the same key from the same password,
+/// the same salt, and the same values for opslimit and memlimit have to be
+/// used.
+///
+/// The function returns `Some(())` on success and `None` if the computation didn't
+/// complete, usually because the operating system refused to allocate the
+/// amount of requested memory.
+pub fn derive_key(key: &mut [u8], passwd: &[u8], &Salt(ref sb): &Salt,
+                  OpsLimit(opslimit): OpsLimit,
+                  MemLimit(memlimit): MemLimit) -> Option<()> {
+    if unsafe {
+        $pwhash_name(key.as_mut_ptr(),
+                     key.len() as c_ulonglong,
+                     passwd.as_ptr(),
+                     passwd.len() as c_ulonglong,
+                     sb,
+                     opslimit as c_ulonglong,
+                     memlimit as size_t,
+                     $alg)
+    } == 0 {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// The `pwhash()` returns a `HashedPassword` which
+/// includes:
+///
+/// - the result of a memory-hard, CPU-intensive hash function applied to the password
+///   `passwd`
+/// - the automatically generated salt used for the
+///   previous computation
+/// - the other parameters required to verify the password: the algorithm,
+///   opslimit and memlimit
+///
+/// `OPSLIMIT_INTERACTIVE` and `MEMLIMIT_INTERACTIVE` are safe baseline
+/// values to use for `opslimit` and `memlimit`.
+///
+/// The function returns `Some(hashed_password)` on success and `None` if it didn't complete
+/// successfully
+pub fn pwhash(passwd: &[u8], OpsLimit(opslimit): OpsLimit,
+              MemLimit(memlimit): MemLimit) -> Option<HashedPassword> {
+    let mut out = HashedPassword([0; STRBYTES]);
+    if unsafe {
+        let HashedPassword(ref mut str_) = out;
+        $str_name(str_,
+                  passwd.as_ptr(),
+                  passwd.len() as c_ulonglong,
+                  opslimit as c_ulonglong,
+                  memlimit as size_t)
+    } == 0 {
+        Some(out)
+    } else {
+        None
+    }
+}
+
+/// `pwhash_verify()` verifies that the password `str_` is a valid password
+/// verification string (as generated by `pwhash()`) for `passwd`
+///
+/// It returns `true` if the verification succeeds, and `false` on error.
+pub fn pwhash_verify(&HashedPassword(ref str_): &HashedPassword,
+                     passwd: &[u8]) -> bool {
+    unsafe {
+        $verify_name(str_,
+                     passwd.as_ptr(),
+                     passwd.len() as c_ulonglong)
+            == 0
+    }
+}
+
+/// `needs_rehash()` checks whether the password verification string `str_`
+/// was computed with the given `opslimit` and `memlimit`
+///
+/// It returns `false` if the parameters match, and `true` if they don't or
+/// if `str_` isn't a valid password verification string for this
+/// algorithm. A stored `HashedPassword` for which `needs_rehash()` returns
+/// `true` should be replaced by a new one computed with `pwhash()` the next
+/// time the password is available, e.g. after a successful
+/// `pwhash_verify()`.
+pub fn needs_rehash(&HashedPassword(ref str_): &HashedPassword,
+                    OpsLimit(opslimit): OpsLimit,
+                    MemLimit(memlimit): MemLimit) -> bool {
+    unsafe {
+        $needs_rehash_name(str_,
+                           opslimit as c_ulonglong,
+                           memlimit as size_t)
+            != 0
+    }
+}
+
+#[cfg(test)]
+mod test_m {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_pwhash_verify() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let pw = randombytes(i);
+            let pwh = pwhash(&pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+            assert!(pwhash_verify(&pwh, &pw));
+        }
+    }
+
+    #[test]
+    fn test_pwhash_verify_tamper() {
+        use randombytes::randombytes;
+        for i in (0..16usize) {
+            let mut pw = randombytes(i);
+            let pwh = pwhash(&pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+            for j in (0..pw.len()) {
+                pw[j] ^= 0x20;
+                assert!(!pwhash_verify(&pwh, &pw));
+                pw[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_pwhash_prefix() {
+        let pwh = pwhash(b"Correct Horse Battery Staple",
+                         OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        assert!(pwh[..].starts_with(STRPREFIX.as_bytes()));
+    }
+
+    #[test]
+    fn test_needs_rehash_memlimit() {
+        // unlike scrypt, Argon2 stores the memory limit as it is
+        let pwh = pwhash(b"Correct Horse Battery Staple",
+                         OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        assert!(needs_rehash(&pwh, OPSLIMIT_INTERACTIVE, MEMLIMIT_MODERATE));
+    }
+
+    #[test]
+    fn test_derive_key_short() {
+        let mut kb = [0u8; 15];
+        let salt = gen_salt();
+        assert!(derive_key(&mut kb, b"Correct Horse Battery Staple", &salt,
+                           OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).is_none());
+    }
+
+    #[test]
+    fn test_serialisation() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let pw = randombytes(i);
+            let pwh = pwhash(&pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+            let salt = gen_salt();
+            round_trip(pwh);
+            round_trip(salt);
+        }
+    }
+}
+
+));
@@ -0,0 +1,57 @@
+//! `crypto_pwhash_argon2i`, the Argon2i variant (version 1.3) of the Argon2
+//! memory-hard function, winner of the
+//! [Password Hashing Competition](https://password-hashing.net/).
+//!
+//! Argon2i uses data-independent memory access, which makes it resistant to
+//! side-channel attacks. New applications should prefer `argon2id13`; this
+//! module is mostly useful to interoperate with existing Argon2i hashes.
+use ffi::{crypto_pwhash_argon2i,
+          crypto_pwhash_argon2i_str,
+          crypto_pwhash_argon2i_str_verify,
+          crypto_pwhash_argon2i_str_needs_rehash,
+          crypto_pwhash_argon2i_SALTBYTES,
+          crypto_pwhash_argon2i_STRBYTES,
+          crypto_pwhash_argon2i_STRPREFIX,
+          crypto_pwhash_argon2i_ALG_ARGON2I13,
+          crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2i_OPSLIMIT_MODERATE,
+          crypto_pwhash_argon2i_MEMLIMIT_MODERATE,
+          crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE,
+          crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE};
+use crypto::pwhash::Algorithm;
+
+argon2_module!(crypto_pwhash_argon2i,
+               crypto_pwhash_argon2i_str,
+               crypto_pwhash_argon2i_str_verify,
+               crypto_pwhash_argon2i_str_needs_rehash,
+               crypto_pwhash_argon2i_SALTBYTES,
+               crypto_pwhash_argon2i_STRBYTES,
+               crypto_pwhash_argon2i_STRPREFIX,
+               Algorithm::Argon2i13,
+               crypto_pwhash_argon2i_ALG_ARGON2I13,
+               crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2i_OPSLIMIT_MODERATE,
+               crypto_pwhash_argon2i_MEMLIMIT_MODERATE,
+               crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE,
+               crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_derive_key() {
+        let mut kb = [0u8; 32];
+        let salt = Salt([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+        let pw = b"Correct Horse Battery Staple";
+        // test vector generated by using libsodium
+        let kb_expected = [0x0d, 0xc6, 0xf6, 0x9f, 0x7b, 0xd3, 0x4f, 0x29,
+                           0xcf, 0xdb, 0x4c, 0xa0, 0x01, 0xae, 0xd7, 0xc7,
+                           0x5f, 0xcd, 0xaa, 0xc6, 0x3e, 0x00, 0x42, 0x67,
+                           0xe2, 0x12, 0xf4, 0xb9, 0x05, 0x8b, 0x5f, 0xca];
+        derive_key(&mut kb, pw, &salt, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        assert_eq!(kb, kb_expected);
+    }
+}
@@ -0,0 +1,57 @@
+//! `crypto_pwhash_argon2id`, the Argon2id variant (version 1.3) of the Argon2
+//! memory-hard function, winner of the
+//! [Password Hashing Competition](https://password-hashing.net/).
+//!
+//! Argon2id combines the data-independent memory access of Argon2i with the
+//! data-dependent memory access of Argon2d, and is the recommended choice for
+//! password hashing and key derivation.
+use ffi::{crypto_pwhash_argon2id,
+          crypto_pwhash_argon2id_str,
+          crypto_pwhash_argon2id_str_verify,
+          crypto_pwhash_argon2id_str_needs_rehash,
+          crypto_pwhash_argon2id_SALTBYTES,
+          crypto_pwhash_argon2id_STRBYTES,
+          crypto_pwhash_argon2id_STRPREFIX,
+          crypto_pwhash_argon2id_ALG_ARGON2ID13,
+          crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2id_OPSLIMIT_MODERATE,
+          crypto_pwhash_argon2id_MEMLIMIT_MODERATE,
+          crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE,
+          crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE};
+use crypto::pwhash::Algorithm;
+
+argon2_module!(crypto_pwhash_argon2id,
+               crypto_pwhash_argon2id_str,
+               crypto_pwhash_argon2id_str_verify,
+               crypto_pwhash_argon2id_str_needs_rehash,
+               crypto_pwhash_argon2id_SALTBYTES,
+               crypto_pwhash_argon2id_STRBYTES,
+               crypto_pwhash_argon2id_STRPREFIX,
+               Algorithm::Argon2id13,
+               crypto_pwhash_argon2id_ALG_ARGON2ID13,
+               crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2id_OPSLIMIT_MODERATE,
+               crypto_pwhash_argon2id_MEMLIMIT_MODERATE,
+               crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE,
+               crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_derive_key() {
+        let mut kb = [0u8; 32];
+        let salt = Salt([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+        let pw = b"Correct Horse Battery Staple";
+        // test vector generated by using libsodium
+        let kb_expected = [0xf9, 0xe7, 0x6c, 0xb3, 0xfd, 0x76, 0xcf, 0x88,
+                           0x50, 0xab, 0xfe, 0x07, 0x44, 0x75, 0x3f, 0xcf,
+                           0xe9, 0xe6, 0xf2, 0x83, 0x58, 0xc9, 0x89, 0x40,
+                           0x67, 0x88, 0x1b, 0xf0, 0xc8, 0x24, 0x41, 0x1b];
+        derive_key(&mut kb, pw, &salt, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        assert_eq!(kb, kb_expected);
+    }
+}
@@ -24,6 +24,24 @@
 //! - Password storage, or rather: storing what it takes to verify a password
 //!   without having to store the actual password.
 //!
+//! # Selected primitive
+//! `pwhash()` is `crypto_pwhash_argon2id`, the Argon2id variant (version 1.3)
+//! of [Argon2](https://github.com/P-H-C/phc-winner-argon2).
+//!
+//! # Alternate primitives
+//!
+//! -------------------------------------------------------------------------
+//! |crypto_pwhash                      |SALTBYTES|STRBYTES|STRPREFIX       |
+//! |-----------------------------------|---------|--------|----------------|
+//! |crypto_pwhash_argon2id             |16       |128     |`$argon2id$`    |
+//! |crypto_pwhash_argon2i              |16       |128     |`$argon2i$`     |
+//! |crypto_pwhash_scryptsalsa208sha256 |32       |102     |`$7$`           |
+//! -------------------------------------------------------------------------
+//!
+//! Hashes produced by one primitive can only be verified by the module that
+//! produced them, or by `verify_any()`, which picks the module from the
+//! prefix of the hash.
+//!
 //! # Example (key derivation)
 //! ```
 //! use sodiumoxide::crypto::secretbox;
@@ -64,5 +82,169 @@
 //!                          pwhash::MEMLIMIT_INTERACTIVE).unwrap();
 //! assert!(pwhash::pwhash_verify(&pwh, passwd));
 //! ```
-pub use self::scryptsalsa208sha256::*;
+//!
+//! # Example (upgrading stored hashes)
+//! ```
+//! use std::str::FromStr;
+//! use sodiumoxide::crypto::pwhash;
+//! use sodiumoxide::crypto::pwhash::{scryptsalsa208sha256, HashedPassword, ParseError};
+//!
+//! fn needs_upgrade(stored: &str) -> bool {
+//!     match HashedPassword::from_str(stored) {
+//!         Ok(pwh) => pwhash::needs_rehash(&pwh,
+//!                                         pwhash::OPSLIMIT_INTERACTIVE,
+//!                                         pwhash::MEMLIMIT_INTERACTIVE),
+//!         // e.g. a hash stored before the switch to Argon2id
+//!         Err(ParseError::WrongAlgorithm) | Err(ParseError::UnknownAlgorithm) => true,
+//!         Err(_) => false,
+//!     }
+//! }
+//!
+//! let passwd = b"Correct Horse Battery Staple";
+//! let old = scryptsalsa208sha256::pwhash(passwd,
+//!                                        scryptsalsa208sha256::OPSLIMIT_INTERACTIVE,
+//!                                        scryptsalsa208sha256::MEMLIMIT_INTERACTIVE).unwrap();
+//! // in reality the hash is loaded from a database, without its zero padding
+//! let stored = old.as_str().to_string();
+//!
+//! if pwhash::verify_any(stored.as_bytes(), passwd) && needs_upgrade(&stored) {
+//!     let new = pwhash::pwhash(passwd,
+//!                              pwhash::OPSLIMIT_INTERACTIVE,
+//!                              pwhash::MEMLIMIT_INTERACTIVE).unwrap();
+//!     // replace the stored hash with `new`
+//!     let stored = new.as_str().to_string();
+//!     assert!(pwhash::verify_any(stored.as_bytes(), passwd));
+//!     assert!(!needs_upgrade(&stored));
+//! }
+//! ```
+//!
+//! # Example (storing and inspecting hashes as strings)
+//! ```
+//! use std::str::FromStr;
+//! use sodiumoxide::crypto::pwhash;
+//!
+//! let passwd = b"Correct Horse Battery Staple";
+//! let pwh = pwhash::pwhash(passwd,
+//!                          pwhash::OPSLIMIT_INTERACTIVE,
+//!                          pwhash::MEMLIMIT_INTERACTIVE).unwrap();
+//! let stored = pwh.as_str().to_string();
+//!
+//! let loaded = pwhash::HashedPassword::from_str(&stored).unwrap();
+//! assert!(pwhash::pwhash_verify(&loaded, passwd));
+//! let info = loaded.info().unwrap();
+//! assert_eq!(info.algorithm, pwhash::Algorithm::Argon2id13);
+//! assert_eq!(info.salt.len(), pwhash::SALTBYTES);
+//! ```
+pub use self::argon2id13::*;
+pub use self::parse::{parse, Algorithm, HashInfo, Params, ParseError};
+#[macro_use]
+mod argon2_macros;
+pub mod argon2id13;
+pub mod argon2i13;
 pub mod scryptsalsa208sha256;
+mod parse;
+
+/// `verify_any()` verifies that `str_` is a valid password verification
+/// string for `passwd`, whichever of the primitives in this module produced it
+///
+/// The primitive is selected from the prefix of `str_`: `$argon2id$`,
+/// `$argon2i$` or `$7$` (scrypt). `str_` may be zero-padded, as returned by
+/// `&hashed_password[..]`, or not.
+///
+/// It returns `true` if the verification succeeds, and `false` if it fails,
+/// if the prefix is unknown or if `str_` is too long for the selected
+/// primitive.
+pub fn verify_any(str_: &[u8], passwd: &[u8]) -> bool {
+    // argon2id13 is re-exported at the top of this module
+    if str_.starts_with(STRPREFIX.as_bytes()) {
+        let mut pwh = HashedPassword([0; STRBYTES]);
+        copy_zero_terminated(&mut pwh.0, str_) &&
+            pwhash_verify(&pwh, passwd)
+    } else if str_.starts_with(argon2i13::STRPREFIX.as_bytes()) {
+        let mut pwh = argon2i13::HashedPassword([0; argon2i13::STRBYTES]);
+        copy_zero_terminated(&mut pwh.0, str_) &&
+            argon2i13::pwhash_verify(&pwh, passwd)
+    } else if str_.starts_with(scryptsalsa208sha256::STRPREFIX.as_bytes()) {
+        let mut pwh = scryptsalsa208sha256::HashedPassword([0; scryptsalsa208sha256::STRBYTES]);
+        copy_zero_terminated(&mut pwh.0, str_) &&
+            scryptsalsa208sha256::pwhash_verify(&pwh, passwd)
+    } else {
+        false
+    }
+}
+
+/// Copies `src` into the zeroed buffer `dst`, making sure that the result is
+/// zero-terminated, as expected by libsodium.
+fn copy_zero_terminated(dst: &mut [u8], src: &[u8]) -> bool {
+    if src.len() > dst.len() {
+        return false;
+    }
+    for (d, &s) in dst.iter_mut().zip(src.iter()) {
+        *d = s;
+    }
+    dst.iter().any(|&b| b == 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_any() {
+        let pw = b"Correct Horse Battery Staple";
+        let id = pwhash(pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        let i = argon2i13::pwhash(pw,
+                                  argon2i13::OPSLIMIT_INTERACTIVE,
+                                  argon2i13::MEMLIMIT_INTERACTIVE).unwrap();
+        let scrypt = scryptsalsa208sha256::pwhash(pw,
+                                                  scryptsalsa208sha256::OPSLIMIT_INTERACTIVE,
+                                                  scryptsalsa208sha256::MEMLIMIT_INTERACTIVE).unwrap();
+        for str_ in [&id[..], &i[..], &scrypt[..]].iter() {
+            assert!(verify_any(str_, pw));
+            assert!(!verify_any(str_, b"Correct Horse Battery Stapl"));
+            // without the zero padding
+            let len = str_.iter().position(|&b| b == 0).unwrap();
+            assert!(verify_any(&str_[..len], pw));
+        }
+    }
+
+    #[test]
+    fn test_verify_any_invalid() {
+        let pw = b"Correct Horse Battery Staple";
+        assert!(!verify_any(b"", pw));
+        assert!(!verify_any(b"$2y$10$abcdefghijklmnopqrstuv", pw));
+        assert!(!verify_any(b"$argon2id$", pw));
+        // no room for the zero terminator
+        let mut long = [b'a'; STRBYTES];
+        for (l, &p) in long.iter_mut().zip(STRPREFIX.as_bytes().iter()) {
+            *l = p;
+        }
+        assert!(!verify_any(&long, pw));
+    }
+
+    // needs_rehash() of every primitive
+    macro_rules! needs_rehash_tests (($test_mod:ident, $primitive:ident) => (
+        mod $test_mod {
+            use crypto::pwhash::$primitive::*;
+
+            #[test]
+            fn test_needs_rehash() {
+                let pwh = pwhash(b"Correct Horse Battery Staple",
+                                 OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+                assert!(!needs_rehash(&pwh, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE));
+                assert!(needs_rehash(&pwh, OPSLIMIT_SENSITIVE, MEMLIMIT_INTERACTIVE));
+                assert!(needs_rehash(&pwh, OPSLIMIT_SENSITIVE, MEMLIMIT_SENSITIVE));
+            }
+
+            #[test]
+            fn test_needs_rehash_invalid() {
+                let pwh = HashedPassword([0; STRBYTES]);
+                assert!(needs_rehash(&pwh, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE));
+            }
+        }
+    ));
+
+    needs_rehash_tests!(test_argon2id13, argon2id13);
+    needs_rehash_tests!(test_argon2i13, argon2i13);
+    needs_rehash_tests!(test_scryptsalsa208sha256, scryptsalsa208sha256);
+}
@@ -0,0 +1,371 @@
+//! Parsing of password verification strings
+use std::error::Error;
+use std::fmt;
+use rustc_serialize::base64::FromBase64;
+use super::{argon2id13, argon2i13, scryptsalsa208sha256};
+
+/// The password hashing primitive that produced a password verification
+/// string
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// `argon2id13`, with the `$argon2id$` prefix
+    Argon2id13,
+    /// `argon2i13`, with the `$argon2i$` prefix
+    Argon2i13,
+    /// `scryptsalsa208sha256`, with the `$7$` prefix
+    ScryptSalsa208Sha256,
+}
+
+/// Cost parameters stored in a password verification string
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Params {
+    /// Argon2 parameters
+    Argon2 {
+        /// Argon2 version, `0x13` (19) for the strings produced by this crate
+        version: u32,
+        /// Number of passes over the memory (`t`), the `OpsLimit`
+        t: u32,
+        /// Amount of memory in kibibytes (`m`), the `MemLimit` divided by 1024
+        m: u32,
+        /// Degree of parallelism (`p`)
+        p: u32,
+    },
+    /// Scrypt parameters
+    Scrypt {
+        /// CPU/memory cost (`N`), always a power of two
+        n: u64,
+        /// Block size (`r`)
+        r: u32,
+        /// Parallelization (`p`)
+        p: u32,
+    },
+}
+
+/// Parsed view of a password verification string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashInfo {
+    /// The primitive that produced the string
+    pub algorithm: Algorithm,
+    /// The cost parameters
+    pub params: Params,
+    /// The salt
+    ///
+    /// For Argon2 these are the decoded salt bytes. Scrypt uses the encoded
+    /// salt as it appears in the string, so these are its ASCII bytes.
+    pub salt: Vec<u8>,
+}
+
+/// Error returned for a malformed password verification string
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string doesn't fit into a `HashedPassword` together with its
+    /// zero terminator
+    TooLong,
+    /// The string doesn't start with the prefix of a known primitive
+    UnknownAlgorithm,
+    /// The string was produced by another primitive than the one it was
+    /// parsed for
+    WrongAlgorithm,
+    /// The string has a known prefix but its parameters, salt or hash are
+    /// malformed
+    Malformed,
+}
+
+impl ParseError {
+    fn message(&self) -> &'static str {
+        match *self {
+            ParseError::TooLong => "password hash string is too long",
+            ParseError::UnknownAlgorithm => "unknown password hashing algorithm",
+            ParseError::WrongAlgorithm => "unexpected password hashing algorithm",
+            ParseError::Malformed => "malformed password hash string",
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+/// `parse()` parses the password verification string `str_`, as produced by
+/// `pwhash()` of any of the primitives in this module, without the zero
+/// terminator
+///
+/// It returns the algorithm, cost parameters and salt found in `str_`, or a
+/// `ParseError` if `str_` is malformed. `parse()` only checks the syntax of
+/// `str_`: whether the parameters are accepted is up to `pwhash_verify()`.
+pub fn parse(str_: &str) -> Result<HashInfo, ParseError> {
+    if str_.starts_with(argon2id13::STRPREFIX) {
+        parse_argon2(Algorithm::Argon2id13, &str_[argon2id13::STRPREFIX.len()..])
+    } else if str_.starts_with(argon2i13::STRPREFIX) {
+        parse_argon2(Algorithm::Argon2i13, &str_[argon2i13::STRPREFIX.len()..])
+    } else if str_.starts_with(scryptsalsa208sha256::STRPREFIX) {
+        parse_scrypt(&str_[scryptsalsa208sha256::STRPREFIX.len()..])
+    } else {
+        Err(ParseError::UnknownAlgorithm)
+    }
+}
+
+/// Checks that `str_` fits into a `HashedPassword` of `strbytes` bytes and
+/// was produced by `algorithm`, as needed by the `from_str()` implementations
+pub fn check(str_: &str, strbytes: usize,
+             algorithm: Algorithm) -> Result<(), ParseError> {
+    if str_.len() >= strbytes {
+        return Err(ParseError::TooLong);
+    }
+    let info = try!(parse(str_));
+    if info.algorithm != algorithm {
+        return Err(ParseError::WrongAlgorithm);
+    }
+    Ok(())
+}
+
+// v=19$m=65536,t=2,p=1$<salt>$<hash>, with the version being optional and
+// salt and hash in unpadded base64
+fn parse_argon2(algorithm: Algorithm, s: &str) -> Result<HashInfo, ParseError> {
+    let fields: Vec<&str> = s.split('$').collect();
+    let (version, rest) = match fields.len() {
+        4 if fields[0].starts_with("v=") => {
+            (try!(decimal(&fields[0][2..])), &fields[1..])
+        }
+        3 => (0x10, &fields[..]),
+        _ => return Err(ParseError::Malformed),
+    };
+    let costs: Vec<&str> = rest[0].split(',').collect();
+    if costs.len() != 3 || !costs[0].starts_with("m=") ||
+       !costs[1].starts_with("t=") || !costs[2].starts_with("p=") {
+        return Err(ParseError::Malformed);
+    }
+    let m = try!(decimal(&costs[0][2..]));
+    let t = try!(decimal(&costs[1][2..]));
+    let p = try!(decimal(&costs[2][2..]));
+    let salt = try!(base64(rest[1]));
+    let hash = try!(base64(rest[2]));
+    if salt.is_empty() || hash.is_empty() {
+        return Err(ParseError::Malformed);
+    }
+    Ok(HashInfo {
+        algorithm: algorithm,
+        params: Params::Argon2 { version: version, t: t, m: m, p: p },
+        salt: salt,
+    })
+}
+
+// one character for log2(N), five characters each for r and p, then the
+// salt and the 32-byte hash, all in scrypt's own base64 alphabet
+fn parse_scrypt(s: &str) -> Result<HashInfo, ParseError> {
+    let b = s.as_bytes();
+    if b.len() < 11 {
+        return Err(ParseError::Malformed);
+    }
+    let n_log2 = try!(itoa64_decode(&b[0..1]));
+    if n_log2 == 0 || n_log2 > 63 {
+        return Err(ParseError::Malformed);
+    }
+    let r = try!(itoa64_decode(&b[1..6]));
+    let p = try!(itoa64_decode(&b[6..11]));
+    let rest = &s[11..];
+    let (salt, hash) = match rest.rfind('$') {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => return Err(ParseError::Malformed),
+    };
+    if hash.len() != 43 || !hash.bytes().all(|c| ITOA64.contains(&c)) {
+        return Err(ParseError::Malformed);
+    }
+    Ok(HashInfo {
+        algorithm: Algorithm::ScryptSalsa208Sha256,
+        params: Params::Scrypt { n: 1u64 << n_log2, r: r, p: p },
+        salt: salt.as_bytes().to_vec(),
+    })
+}
+
+fn decimal(s: &str) -> Result<u32, ParseError> {
+    if s.is_empty() || !s.bytes().all(|c| c >= b'0' && c <= b'9') {
+        return Err(ParseError::Malformed);
+    }
+    s.parse().map_err(|_| ParseError::Malformed)
+}
+
+fn base64(s: &str) -> Result<Vec<u8>, ParseError> {
+    let valid = s.bytes().all(|c| {
+        (c >= b'A' && c <= b'Z') || (c >= b'a' && c <= b'z') ||
+        (c >= b'0' && c <= b'9') || c == b'+' || c == b'/'
+    });
+    if !valid || s.len() % 4 == 1 {
+        return Err(ParseError::Malformed);
+    }
+    s.from_base64().map_err(|_| ParseError::Malformed)
+}
+
+const ITOA64: &'static [u8] =
+    b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+// little-endian, six bits per character, at most 30 bits
+fn itoa64_decode(s: &[u8]) -> Result<u32, ParseError> {
+    let mut value = 0u32;
+    for (i, c) in s.iter().enumerate() {
+        match ITOA64.iter().position(|a| a == c) {
+            Some(v) => value |= (v as u32) << (6 * i),
+            None => return Err(ParseError::Malformed),
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // from test/default/pwhash_argon2id.c in libsodium
+    const ARGON2ID_STR: &'static str =
+        "$argon2id$v=19$m=4096,t=1,p=3$PkEgcHJldHR5IGxvbmcgc2FsdA\
+         $HUqx5Z1b/ZypnUrvvJ5UC2Q+T6Q1WwASK/Kr9dRbGA0";
+    // from test/default/pwhash_scrypt.c in libsodium
+    const SCRYPT_STR: &'static str =
+        "$7$B6....1....75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD\
+         $tc8RoB3.Em3/zNgMLWo2u00oGIoTyJv4fl3Fl8Tix72";
+
+    #[test]
+    fn test_parse_argon2id() {
+        let info = parse(ARGON2ID_STR).unwrap();
+        assert_eq!(info.algorithm, Algorithm::Argon2id13);
+        assert_eq!(info.params, Params::Argon2 { version: 19, t: 1, m: 4096, p: 3 });
+        assert_eq!(&info.salt[..], &b">A pretty long salt"[..]);
+    }
+
+    #[test]
+    fn test_parse_argon2i() {
+        // from test/default/pwhash_argon2i.c in libsodium
+        let info = parse("$argon2i$v=19$m=4096,t=3,p=2$X1NhbHQAAAAAAAAAAAAAAA\
+                          $z/QMiU4lQxGsYNc/+K/bizwsA1P11UG2dj/7+aILJ4I").unwrap();
+        assert_eq!(info.algorithm, Algorithm::Argon2i13);
+        assert_eq!(info.params, Params::Argon2 { version: 19, t: 3, m: 4096, p: 2 });
+        assert_eq!(&info.salt[..], &b"_Salt\0\0\0\0\0\0\0\0\0\0\0"[..]);
+    }
+
+    #[test]
+    fn test_parse_argon2_no_version() {
+        let info = parse("$argon2i$m=4096,t=3,p=2$X1NhbHQAAAAAAAAAAAAAAA\
+                          $z/QMiU4lQxGsYNc/+K/bizwsA1P11UG2dj/7+aILJ4I").unwrap();
+        assert_eq!(info.params, Params::Argon2 { version: 16, t: 3, m: 4096, p: 2 });
+    }
+
+    #[test]
+    fn test_parse_scrypt() {
+        let info = parse(SCRYPT_STR).unwrap();
+        assert_eq!(info.algorithm, Algorithm::ScryptSalsa208Sha256);
+        assert_eq!(info.params, Params::Scrypt { n: 8192, r: 8, p: 3 });
+        assert_eq!(&info.salt[..], &b"75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD"[..]);
+    }
+
+    #[test]
+    fn test_parse_generated() {
+        let pw = b"Correct Horse Battery Staple";
+        let pwh = argon2id13::pwhash(pw, argon2id13::OPSLIMIT_INTERACTIVE,
+                                     argon2id13::MEMLIMIT_INTERACTIVE).unwrap();
+        let info = parse(pwh.as_str()).unwrap();
+        assert_eq!(info.params, Params::Argon2 { version: 19, t: 2, m: 65536, p: 1 });
+        assert_eq!(info.salt.len(), argon2id13::SALTBYTES);
+        let pwh = scryptsalsa208sha256::pwhash(pw,
+                                               scryptsalsa208sha256::OPSLIMIT_INTERACTIVE,
+                                               scryptsalsa208sha256::MEMLIMIT_INTERACTIVE)
+            .unwrap();
+        let info = parse(pwh.as_str()).unwrap();
+        assert_eq!(info.params, Params::Scrypt { n: 16384, r: 8, p: 1 });
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let malformed = [
+            "$argon2id$",
+            "$argon2id$v=19$m=65536,t=2,p=1c29tZXNhbHQ",
+            "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ",
+            "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$",
+            "$argon2id$v=19$m=65536,t=2$c29tZXNhbHQ$aGFzaA",
+            "$argon2id$v=19$t=2,m=65536,p=1$c29tZXNhbHQ$aGFzaA",
+            "$argon2id$v=19$m=+65536,t=2,p=1$c29tZXNhbHQ$aGFzaA",
+            "$argon2id$v=19$m=65536,t=99999999999,p=1$c29tZXNhbHQ$aGFzaA",
+            "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ=$aGFzaA",
+            "$argon2id$v=19$m=65536,t=2,p=1$c29t*XNhbHQ$aGFzaA",
+            "$argon2id$x=19$m=65536,t=2,p=1$c29tZXNhbHQ$aGFzaA",
+            "$7$",
+            "$7$B6....1....",
+            "$7$B6....1....75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD",
+            "$7$B6....1....75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD$tc8RoB3",
+            "$7$.6....1....75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD\
+             $tc8RoB3.Em3/zNgMLWo2u00oGIoTyJv4fl3Fl8Tix72",
+            "$7$B6..*.1....75gBMAGwfFWZqBdyF3WdTQnWdUsuTiWjG1fF9c1jiSD\
+             $tc8RoB3.Em3/zNgMLWo2u00oGIoTyJv4fl3Fl8Tix72",
+        ];
+        for s in malformed.iter() {
+            assert_eq!(parse(s), Err(ParseError::Malformed));
+        }
+        assert_eq!(parse(""), Err(ParseError::UnknownAlgorithm));
+        assert_eq!(parse("$2y$10$abcdefghijklmnopqrstuv"), Err(ParseError::UnknownAlgorithm));
+        assert_eq!(parse("$argon2d$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$aGFzaA"),
+                   Err(ParseError::UnknownAlgorithm));
+    }
+
+    // from_str(), as_str() and info() of the HashedPassword of every primitive
+    macro_rules! hashed_password_str_tests (($test_mod:ident, $primitive:ident,
+                                              $algorithm:expr, $other:expr) => (
+        mod $test_mod {
+            use std::iter::repeat;
+            use std::str::FromStr;
+            use crypto::pwhash::{parse, Algorithm, ParseError};
+            use crypto::pwhash::$primitive::{HashedPassword, pwhash, STRBYTES, STRPREFIX,
+                                             OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE};
+
+            #[test]
+            fn test_from_str_as_str() {
+                let pwh = pwhash(b"Correct Horse Battery Staple",
+                                 OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+                let s = pwh.as_str();
+                assert!(s.starts_with(STRPREFIX));
+                assert!(s.len() < STRBYTES);
+                assert!(HashedPassword::from_str(s).unwrap() == pwh);
+                assert!(HashedPassword::from_str(s).unwrap().as_str() == s);
+                let padded = String::from_utf8(pwh[..].to_vec()).unwrap();
+                assert!(padded.parse::<HashedPassword>().unwrap() == pwh);
+            }
+
+            #[test]
+            fn test_from_str_invalid() {
+                let pwh = pwhash(b"Correct Horse Battery Staple",
+                                 OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+                let s = pwh.as_str();
+                assert!(HashedPassword::from_str(&s[..s.len() - 44]).err() ==
+                        Some(ParseError::Malformed));
+                assert!(HashedPassword::from_str("").err() == Some(ParseError::UnknownAlgorithm));
+                let long: String = s.chars().chain(repeat('a').take(STRBYTES - s.len())).collect();
+                assert!(HashedPassword::from_str(&long).err() == Some(ParseError::TooLong));
+                assert!(HashedPassword::from_str($other).err() ==
+                        Some(ParseError::WrongAlgorithm));
+            }
+
+            #[test]
+            fn test_info() {
+                let pwh = pwhash(b"Correct Horse Battery Staple",
+                                 OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+                let info = pwh.info().unwrap();
+                assert!(info.algorithm == $algorithm);
+                assert!(info == parse(pwh.as_str()).unwrap());
+                assert!(HashedPassword([0; STRBYTES]).info().is_err());
+            }
+        }
+    ));
+
+    hashed_password_str_tests!(test_argon2id13, argon2id13,
+                               Algorithm::Argon2id13, super::SCRYPT_STR);
+    hashed_password_str_tests!(test_argon2i13, argon2i13,
+                               Algorithm::Argon2i13, super::SCRYPT_STR);
+    hashed_password_str_tests!(test_scryptsalsa208sha256, scryptsalsa208sha256,
+                               Algorithm::ScryptSalsa208Sha256, super::ARGON2ID_STR);
+}
@@ -4,6 +4,7 @@ use ffi;
 use randombytes::randombytes_into;
 use libc::{c_ulonglong, size_t};
 use rustc_serialize;
+use crypto::pwhash::Algorithm;
 
 pub const SALTBYTES: usize = ffi::crypto_pwhash_scryptsalsa208sha256_SALTBYTES;
 pub const STRBYTES: usize = ffi::crypto_pwhash_scryptsalsa208sha256_STRBYTES;
@@ -48,6 +49,7 @@ newtype_impl!(Salt, SALTBYTES);
 pub struct HashedPassword(pub [u8; STRBYTES]);
 newtype_clone!(HashedPassword);
 newtype_impl!(HashedPassword, STRBYTES);
+hashed_password_str_impl!(STRBYTES, Algorithm::ScryptSalsa208Sha256);
 
 /// `gen_salt()` randombly generates a new `Salt` for key derivation
 ///
@@ -154,6 +156,26 @@ pub fn pwhash_verify(&HashedPassword(ref str_): &HashedPassword,
     }
 }
 
+/// `needs_rehash()` checks whether the password verification string `str_`
+/// was computed with the given `opslimit` and `memlimit`
+///
+/// It returns `false` if the parameters match, and `true` if they don't or
+/// if `str_` isn't a valid password verification string for this
+/// algorithm. A stored `HashedPassword` for which `needs_rehash()` returns
+/// `true` should be replaced by a new one computed with `pwhash()` the next
+/// time the password is available, e.g. after a successful
+/// `pwhash_verify()`.
+pub fn needs_rehash(&HashedPassword(ref str_): &HashedPassword,
+                    OpsLimit(opslimit): OpsLimit,
+                    MemLimit(memlimit): MemLimit) -> bool {
+    unsafe {
+        ffi::crypto_pwhash_scryptsalsa208sha256_str_needs_rehash(str_,
+                                                                 opslimit as c_ulonglong,
+                                                                 memlimit as size_t)
+            != 0
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
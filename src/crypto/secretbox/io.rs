@@ -0,0 +1,404 @@
+//! Chunked encryption of byte streams with `std::io` adapters
+//!
+//! `EncryptWriter` splits the data written to it into chunks of `CHUNKBYTES`
+//! bytes and seals each of them with `secretbox`; `DecryptReader` reads the
+//! chunks back, verifies and decrypts them. Only one chunk is kept in memory
+//! at a time.
+//!
+//! The nonce of every chunk is derived from the `Nonce` given to `new()`: its
+//! first 15 bytes are kept, followed by the index of the chunk as a 64 bit
+//! big-endian integer and a byte that is `1` for the last chunk and `0`
+//! otherwise. Because of this:
+//!
+//! - chunks can't be reordered, duplicated or dropped without the reader
+//!   noticing;
+//! - a stream that was cut at a chunk boundary is detected, since its last
+//!   chunk isn't flagged as final;
+//! - a `Key` and `Nonce` pair must not be used for more than one stream. A
+//!   randomly generated nonce (`gen_nonce()`) can be stored in front of the
+//!   encrypted data.
+//!
+//! Every chunk but the last one is `CHUNKBYTES + MACBYTES` bytes long. The
+//! last chunk holds between 1 and `CHUNKBYTES` bytes of data, or none at all
+//! if nothing was written.
+use std::cmp;
+use std::io;
+use std::io::{Read, Write};
+use super::xsalsa20poly1305::{Key, Nonce, MACBYTES, NONCEBYTES,
+                              seal_inplace, open_inplace};
+
+/// Number of plaintext bytes in every chunk but the last one
+pub const CHUNKBYTES: usize = 65536;
+
+const CIPHERCHUNKBYTES: usize = CHUNKBYTES + MACBYTES;
+
+fn chunk_nonce(&Nonce(ref n): &Nonce, counter: u64, last: bool) -> Nonce {
+    let mut cn = *n;
+    for i in (0..8) {
+        cn[NONCEBYTES - 9 + i] = (counter >> (56 - 8 * i)) as u8;
+    }
+    cn[NONCEBYTES - 1] = if last { 1 } else { 0 };
+    Nonce(cn)
+}
+
+/// `EncryptWriter` encrypts everything written to it and writes the sealed
+/// chunks to the underlying writer
+///
+/// `finish()` must be called once all the data has been written, to write
+/// the last chunk. A stream that hasn't been finished is reported as
+/// truncated by `DecryptReader`.
+///
+/// Once writing a chunk to the underlying writer has failed, the stream is
+/// left in an unknown state: every later call to `write()`, `flush()` or
+/// `finish()` fails as well.
+pub struct EncryptWriter<W: Write> {
+    inner: W,
+    key: Key,
+    nonce: Nonce,
+    counter: u64,
+    buf: Vec<u8>,
+    poisoned: bool,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    /// `new()` creates an `EncryptWriter` writing to `inner`, with the secret
+    /// key `k` and the nonce `n`
+    pub fn new(inner: W, n: &Nonce, k: &Key) -> EncryptWriter<W> {
+        EncryptWriter {
+            inner: inner,
+            key: k.clone(),
+            nonce: *n,
+            counter: 0,
+            buf: Vec::with_capacity(CIPHERCHUNKBYTES),
+            poisoned: false,
+        }
+    }
+
+    fn check_poisoned(&self) -> io::Result<()> {
+        if self.poisoned {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "a previous write to the underlying writer failed"));
+        }
+        Ok(())
+    }
+
+    fn write_chunk(&mut self, last: bool) -> io::Result<()> {
+        try!(self.check_poisoned());
+        let n = chunk_nonce(&self.nonce, self.counter, last);
+        // the buffer holds the sealed chunk from here on, and part of it may
+        // already have been written if `write_all()` fails
+        self.poisoned = true;
+        seal_inplace(&mut self.buf, &n, &self.key);
+        try!(self.inner.write_all(&self.buf));
+        self.poisoned = false;
+        self.buf.clear();
+        self.counter += 1;
+        Ok(())
+    }
+
+    /// `finish()` encrypts and writes the remaining data as the last chunk,
+    /// flushes the underlying writer and returns it
+    pub fn finish(mut self) -> io::Result<W> {
+        try!(self.write_chunk(true));
+        try!(self.inner.flush());
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        try!(self.check_poisoned());
+        // a full chunk is only written once more data arrives, since the
+        // last chunk has to be flagged as such
+        if self.buf.len() == CHUNKBYTES && !buf.is_empty() {
+            try!(self.write_chunk(false));
+        }
+        let len = cmp::min(buf.len(), CHUNKBYTES - self.buf.len());
+        self.buf.extend(buf[..len].iter().cloned());
+        Ok(len)
+    }
+
+    /// `flush()` only flushes the underlying writer: buffered data is written
+    /// once a full chunk is available, or by `finish()`
+    fn flush(&mut self) -> io::Result<()> {
+        try!(self.check_poisoned());
+        self.inner.flush()
+    }
+}
+
+/// `DecryptReader` reads chunks written by an `EncryptWriter` from the
+/// underlying reader, and returns the decrypted data
+///
+/// Reading fails with an error of kind `io::ErrorKind::InvalidData` if a
+/// chunk fails verification, if the stream is truncated or if data follows
+/// the last chunk. Data returned before the error was authenticated, but
+/// the stream as a whole is only authentic once `read()` has returned `0`.
+pub struct DecryptReader<R: Read> {
+    inner: R,
+    key: Key,
+    nonce: Nonce,
+    counter: u64,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<R: Read> DecryptReader<R> {
+    /// `new()` creates a `DecryptReader` reading from `inner`, with the secret
+    /// key `k` and the nonce `n`
+    pub fn new(inner: R, n: &Nonce, k: &Key) -> DecryptReader<R> {
+        DecryptReader {
+            inner: inner,
+            key: k.clone(),
+            nonce: *n,
+            counter: 0,
+            buf: Vec::with_capacity(CIPHERCHUNKBYTES),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// `into_inner()` returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fill_buf(&mut self) -> io::Result<()> {
+        self.buf.clear();
+        self.buf.resize(CIPHERCHUNKBYTES, 0);
+        let mut len = 0;
+        while len < CIPHERCHUNKBYTES {
+            match self.inner.read(&mut self.buf[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(e),
+            }
+        }
+        self.buf.truncate(len);
+        Ok(())
+    }
+
+    fn read_chunk(&mut self) -> io::Result<()> {
+        try!(self.fill_buf());
+        // a full chunk is usually followed by more chunks, but can also be
+        // the last one
+        let mut last = self.buf.len() < CIPHERCHUNKBYTES;
+        let mut ok = !last && open_inplace(&mut self.buf,
+                                           &chunk_nonce(&self.nonce, self.counter, false),
+                                           &self.key).is_ok();
+        if !ok {
+            last = true;
+            ok = open_inplace(&mut self.buf,
+                              &chunk_nonce(&self.nonce, self.counter, true),
+                              &self.key).is_ok();
+        }
+        if !ok {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      "chunk failed verification or stream truncated"));
+        }
+        if last {
+            let mut extra = [0u8; 1];
+            if try!(self.inner.read(&mut extra)) != 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "data after the last chunk"));
+            }
+            self.done = true;
+        }
+        self.pos = MACBYTES;
+        self.counter += 1;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.buf.len() {
+            if self.done {
+                return Ok(0);
+            }
+            if let Err(e) = self.read_chunk() {
+                // never hand out what is left of a chunk that failed
+                self.buf.clear();
+                self.pos = 0;
+                return Err(e);
+            }
+        }
+        let len = cmp::min(buf.len(), self.buf.len() - self.pos);
+        for (b, p) in buf.iter_mut().zip(self.buf[self.pos..self.pos + len].iter()) {
+            *b = *p;
+        }
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crypto::secretbox::xsalsa20poly1305::{Key, Nonce, gen_key, gen_nonce, MACBYTES};
+    use std::io;
+    use std::io::{Read, Write};
+
+    fn encrypt(m: &[u8], piece: usize, n: &Nonce, k: &Key) -> Vec<u8> {
+        let mut w = EncryptWriter::new(Vec::new(), n, k);
+        for p in m.chunks(piece) {
+            w.write_all(p).unwrap();
+        }
+        w.finish().unwrap()
+    }
+
+    fn decrypt(c: &[u8], n: &Nonce, k: &Key) -> Result<Vec<u8>, ()> {
+        let mut r = DecryptReader::new(c, n, k);
+        let mut m = Vec::new();
+        match r.read_to_end(&mut m) {
+            Ok(_) => Ok(m),
+            Err(_) => Err(()),
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        use randombytes::randombytes;
+        let sizes = [0, 1, 1000, CHUNKBYTES - 1, CHUNKBYTES, CHUNKBYTES + 1,
+                     3 * CHUNKBYTES, 3 * CHUNKBYTES + 17];
+        for s in sizes.iter() {
+            let k = gen_key();
+            let n = gen_nonce();
+            let m = randombytes(*s);
+            for piece in [1000, 4097, CHUNKBYTES + 3].iter() {
+                let c = encrypt(&m, *piece, &n, &k);
+                let chunks = if *s == 0 { 1 } else { (*s + CHUNKBYTES - 1) / CHUNKBYTES };
+                assert!(c.len() == s + chunks * MACBYTES);
+                assert!(Ok(m.clone()) == decrypt(&c, &n, &k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrong_key_or_nonce() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let c = encrypt(b"some data", 4, &n, &k);
+        assert!(decrypt(&c, &gen_nonce(), &k).is_err());
+        assert!(decrypt(&c, &n, &gen_key()).is_err());
+    }
+
+    #[test]
+    fn test_truncated() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(3 * CHUNKBYTES);
+        let c = encrypt(&m, CHUNKBYTES, &n, &k);
+        // cut at every chunk boundary and in the middle of a chunk
+        for i in (0..3) {
+            let end = i * (CHUNKBYTES + MACBYTES);
+            assert!(decrypt(&c[..end], &n, &k).is_err());
+            assert!(decrypt(&c[..end + 100], &n, &k).is_err());
+        }
+        assert!(decrypt(&c[..c.len() - 1], &n, &k).is_err());
+    }
+
+    #[test]
+    fn test_not_finished() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(2 * CHUNKBYTES + 1);
+        let mut c = Vec::new();
+        {
+            let mut w = EncryptWriter::new(&mut c, &n, &k);
+            w.write_all(&m).unwrap();
+        }
+        assert!(c.len() == 2 * (CHUNKBYTES + MACBYTES));
+        assert!(decrypt(&c, &n, &k).is_err());
+    }
+
+    #[test]
+    fn test_reordered() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(2 * CHUNKBYTES + 1);
+        let c = encrypt(&m, CHUNKBYTES, &n, &k);
+        let l = CHUNKBYTES + MACBYTES;
+        let mut c2 = Vec::new();
+        c2.extend(c[l..2 * l].iter().cloned());
+        c2.extend(c[..l].iter().cloned());
+        c2.extend(c[2 * l..].iter().cloned());
+        assert!(decrypt(&c2, &n, &k).is_err());
+    }
+
+    #[test]
+    fn test_trailing_data() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut c = encrypt(b"some data", 4, &n, &k);
+        assert!(decrypt(&c, &n, &k).is_ok());
+        c.push(0);
+        assert!(decrypt(&c, &n, &k).is_err());
+    }
+
+    struct FailOnce {
+        out: Vec<u8>,
+        failed: bool,
+    }
+
+    impl Write for FailOnce {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if !self.failed {
+                self.failed = true;
+                return Err(io::Error::new(io::ErrorKind::Other, "failing once"));
+            }
+            self.out.extend(buf.iter().cloned());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_after_error() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(CHUNKBYTES + 1);
+        let mut w = EncryptWriter::new(FailOnce { out: Vec::new(), failed: false }, &n, &k);
+        assert!(w.write_all(&m[..CHUNKBYTES]).is_ok());
+        assert!(w.write(&m[CHUNKBYTES..]).is_err());
+        assert!(w.write(&m[CHUNKBYTES..]).is_err());
+        assert!(w.flush().is_err());
+        assert!(w.finish().is_err());
+
+        let mut w = EncryptWriter::new(FailOnce { out: Vec::new(), failed: false }, &n, &k);
+        assert!(w.write_all(b"some data").is_ok());
+        assert!(w.finish().is_err());
+    }
+
+    #[test]
+    fn test_read_after_error() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut c = encrypt(b"some data", 4, &n, &k);
+        c[MACBYTES] ^= 0x20;
+        let mut r = DecryptReader::new(&c[..], &n, &k);
+        let mut buf = [0u8; 16];
+        assert!(r.read(&mut buf).is_err());
+        assert!(r.read(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_tamper() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut c = encrypt(b"some data", 4, &n, &k);
+        for i in (0..c.len()) {
+            c[i] ^= 0x20;
+            assert!(decrypt(&c, &n, &k).is_err());
+            c[i] ^= 0x20;
+        }
+    }
+}
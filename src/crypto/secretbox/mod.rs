@@ -32,5 +32,34 @@
 //! let their_plaintext = secretbox::open(&ciphertext, &nonce, &key).unwrap();
 //! assert!(plaintext == &their_plaintext[..]);
 //! ```
+//!
+//! # Example (detached tag)
+//! ```
+//! use sodiumoxide::crypto::secretbox;
+//! let key = secretbox::gen_key();
+//! let nonce = secretbox::gen_nonce();
+//! let plaintext = b"some data";
+//! // the tag can be stored apart from the ciphertext
+//! let (ciphertext, tag) = secretbox::seal_detached(plaintext, &nonce, &key);
+//! let their_plaintext = secretbox::open_detached(&ciphertext, &tag, &nonce, &key).unwrap();
+//! assert!(plaintext == &their_plaintext[..]);
+//! ```
+//!
+//! # Example (encrypting a stream in chunks)
+//! ```
+//! use std::io::{Read, Write};
+//! use sodiumoxide::crypto::secretbox;
+//! use sodiumoxide::crypto::secretbox::io::{EncryptWriter, DecryptReader};
+//! let key = secretbox::gen_key();
+//! let nonce = secretbox::gen_nonce();
+//! let mut writer = EncryptWriter::new(Vec::new(), &nonce, &key);
+//! writer.write_all(b"some data").unwrap();
+//! let ciphertext = writer.finish().unwrap();
+//! let mut reader = DecryptReader::new(&ciphertext[..], &nonce, &key);
+//! let mut their_plaintext = Vec::new();
+//! reader.read_to_end(&mut their_plaintext).unwrap();
+//! assert!(&their_plaintext[..] == b"some data");
+//! ```
 pub use self::xsalsa20poly1305::*;
 pub mod xsalsa20poly1305;
+pub mod io;
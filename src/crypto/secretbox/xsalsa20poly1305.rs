@@ -5,12 +5,16 @@
 //! This function is conjectured to meet the standard notions of privacy and
 //! authenticity.
 use ffi;
-use marshal::marshal;
+use crypto::aead::Aead;
+use libc::c_ulonglong;
 use randombytes::randombytes_into;
 use rustc_serialize;
+use std::iter::repeat;
+use std::ptr;
 
 pub const KEYBYTES: usize = ffi::crypto_secretbox_xsalsa20poly1305_KEYBYTES;
 pub const NONCEBYTES: usize = ffi::crypto_secretbox_xsalsa20poly1305_NONCEBYTES;
+pub const MACBYTES: usize = ffi::crypto_secretbox_xsalsa20poly1305_MACBYTES;
 
 /// `Key` for symmetric authenticated encryption
 ///
@@ -29,8 +33,13 @@ pub struct Nonce(pub [u8; NONCEBYTES]);
 newtype_clone!(Nonce);
 newtype_impl!(Nonce, NONCEBYTES);
 
-const ZEROBYTES: usize = 32;
-const BOXZEROBYTES: usize = 16;
+/// Authentication `Tag` for the detached form of symmetric authenticated
+/// encryption
+#[derive(Copy)]
+pub struct Tag(pub [u8; MACBYTES]);
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, MACBYTES);
 
 /// `gen_key()` randomly generates a secret key
 ///
@@ -56,15 +65,20 @@ pub fn gen_nonce() -> Nonce {
 
 /// `seal()` encrypts and authenticates a message `m` using a secret key `k` and a
 /// nonce `n`.  It returns a ciphertext `c`.
+///
+/// The ciphertext is the authentication tag followed by the encrypted
+/// message, so it is `MACBYTES` longer than `m`.
 pub fn seal(m: &[u8],
             &Nonce(ref n): &Nonce,
             &Key(ref k): &Key) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_secretbox_xsalsa20poly1305(dst, src, len,
-                                                   n, k)
-        }
-    });
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        ffi::crypto_secretbox_easy(c.as_mut_ptr(),
+                                   m.as_ptr(),
+                                   m.len() as c_ulonglong,
+                                   n,
+                                   k);
+    }
     c
 }
 
@@ -74,18 +88,114 @@ pub fn seal(m: &[u8],
 pub fn open(c: &[u8],
             &Nonce(ref n): &Nonce,
             &Key(ref k): &Key) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
+    if c.len() < MACBYTES {
         return None;
     }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_secretbox_xsalsa20poly1305_open(dst,
-                                                        src,
-                                                        len,
-                                                        n,
-                                                        k)
-        }
-    });
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        ffi::crypto_secretbox_open_easy(m.as_mut_ptr(),
+                                        c.as_ptr(),
+                                        c.len() as c_ulonglong,
+                                        n,
+                                        k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// `seal_inplace()` encrypts and authenticates the message held in `buf` using a
+/// secret key `k` and a nonce `n`. On return `buf` holds the ciphertext, laid out
+/// the same way as the output of `seal()`.
+///
+/// `buf` is only reallocated if its capacity is less than `MACBYTES` more than
+/// its length.
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &Key(ref k): &Key) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        let p = buf.as_mut_ptr();
+        let pm = p.offset(MACBYTES as isize);
+        ptr::copy(p, pm, mlen);
+        ffi::crypto_secretbox_detached(pm,
+                                       p as *mut [u8; MACBYTES],
+                                       pm,
+                                       mlen as c_ulonglong,
+                                       n,
+                                       k);
+    }
+}
+
+/// `open_inplace()` verifies and decrypts a ciphertext `c`, as produced by `seal()`,
+/// using a secret key `k` and a nonce `n`. The message is decrypted in place and
+/// `Ok(m)` is returned, where `m` is the plaintext part of `c`.
+/// If the ciphertext fails verification, `open_inplace()` returns `Err(())` and
+/// leaves `c` untouched.
+pub fn open_inplace<'a>(c: &'a mut [u8],
+                        &Nonce(ref n): &Nonce,
+                        &Key(ref k): &Key) -> Result<&'a [u8], ()> {
+    if c.len() < MACBYTES {
+        return Err(());
+    }
+    let ret = unsafe {
+        let p = c.as_mut_ptr();
+        let pc = p.offset(MACBYTES as isize);
+        ffi::crypto_secretbox_open_detached(pc,
+                                            pc,
+                                            p as *const [u8; MACBYTES],
+                                            (c.len() - MACBYTES) as c_ulonglong,
+                                            n,
+                                            k)
+    };
+    if ret == 0 {
+        Ok(&c[MACBYTES..])
+    } else {
+        Err(())
+    }
+}
+
+/// `seal_detached()` encrypts and authenticates a message `m` using a secret key `k`
+/// and a nonce `n`. It returns the encrypted message `c`, which has the same
+/// length as `m`, and the authentication tag separately.
+///
+/// `seal()` returns the tag followed by `c`.
+pub fn seal_detached(m: &[u8],
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> (Vec<u8>, Tag) {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        ffi::crypto_secretbox_detached(c.as_mut_ptr(),
+                                       &mut tag,
+                                       m.as_ptr(),
+                                       m.len() as c_ulonglong,
+                                       n,
+                                       k);
+    }
+    (c, Tag(tag))
+}
+
+/// `open_detached()` verifies and decrypts an encrypted message `c` and its
+/// authentication tag `tag` using a secret key `k` and a nonce `n`.
+/// It returns a plaintext `Some(m)`.
+/// If the ciphertext fails verification, `open_detached()` returns `None`.
+pub fn open_detached(c: &[u8],
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Option<Vec<u8>> {
+    let mut m: Vec<u8> = repeat(0u8).take(c.len()).collect();
+    let ret = unsafe {
+        ffi::crypto_secretbox_open_detached(m.as_mut_ptr(),
+                                            c.as_ptr(),
+                                            tag,
+                                            c.len() as c_ulonglong,
+                                            n,
+                                            k)
+    };
     if ret == 0 {
         Some(m)
     } else {
@@ -93,6 +203,74 @@ pub fn open(c: &[u8],
     }
 }
 
+/// Marker type implementing the `Aead` trait for `crypto_secretbox_xsalsa20poly1305`
+///
+/// `crypto_secretbox_xsalsa20poly1305` does not support additional data: the
+/// `Aead` functions fail when given a non-empty `ad`.
+#[derive(Clone, Copy)]
+pub struct XSalsa20Poly1305;
+
+fn no_ad(ad: Option<&[u8]>) -> bool {
+    ad.map(|ad| ad.is_empty()).unwrap_or(true)
+}
+
+impl Aead for XSalsa20Poly1305 {
+    type Key = Key;
+    type Nonce = Nonce;
+    type Tag = Tag;
+
+    const KEYBYTES: usize = KEYBYTES;
+    const NONCEBYTES: usize = NONCEBYTES;
+    const TAGBYTES: usize = MACBYTES;
+
+    fn key_from_slice(bs: &[u8]) -> Option<Key> {
+        Key::from_slice(bs)
+    }
+
+    fn nonce_from_slice(bs: &[u8]) -> Option<Nonce> {
+        Nonce::from_slice(bs)
+    }
+
+    fn seal(m: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Result<Vec<u8>, ()> {
+        if no_ad(ad) {
+            Ok(seal(m, n, k))
+        } else {
+            Err(())
+        }
+    }
+
+    fn open(c: &[u8], ad: Option<&[u8]>, n: &Nonce, k: &Key) -> Option<Vec<u8>> {
+        if no_ad(ad) {
+            open(c, n, k)
+        } else {
+            None
+        }
+    }
+
+    fn seal_in_place(buf: &mut Vec<u8>,
+                     ad: Option<&[u8]>,
+                     n: &Nonce,
+                     k: &Key) -> Result<(), ()> {
+        if no_ad(ad) {
+            seal_inplace(buf, n, k);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn open_in_place<'a>(c: &'a mut [u8],
+                         ad: Option<&[u8]>,
+                         n: &Nonce,
+                         k: &Key) -> Result<&'a [u8], ()> {
+        if no_ad(ad) {
+            open_inplace(c, n, k)
+        } else {
+            Err(())
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -127,6 +305,111 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_seal_open_detached() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let (c, tag) = seal_detached(&m, &n, &k);
+            assert!(c.len() == m.len());
+            let opened = open_detached(&c, &tag, &n, &k);
+            assert!(Some(m) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_detached_same_as_seal() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let c = seal(&m, &n, &k);
+            let (cd, Tag(tag)) = seal_detached(&m, &n, &k);
+            assert!(&c[..MACBYTES] == &tag[..]);
+            assert!(&c[MACBYTES..] == &cd[..]);
+        }
+    }
+
+    #[test]
+    fn test_seal_open_detached_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let (mut c, Tag(mut tag)) = seal_detached(&m, &n, &k);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                assert!(None == open_detached(&c, &Tag(tag), &n, &k));
+                c[j] ^= 0x20;
+            }
+            for j in (0..tag.len()) {
+                tag[j] ^= 0x20;
+                assert!(None == open_detached(&c, &Tag(tag), &n, &k));
+                tag[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seal_open_inplace() {
+        use randombytes::randombytes;
+        for i in (0..256usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let mut buf = m.clone();
+            seal_inplace(&mut buf, &n, &k);
+            assert!(buf == seal(&m, &n, &k));
+            let opened = open_inplace(&mut buf, &n, &k);
+            assert!(Ok(&m[..]) == opened);
+        }
+    }
+
+    #[test]
+    fn test_seal_inplace_no_realloc() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut buf = Vec::with_capacity(64 + MACBYTES);
+        buf.extend(b"some data".iter().cloned());
+        let p = buf.as_ptr();
+        seal_inplace(&mut buf, &n, &k);
+        assert!(p == buf.as_ptr());
+    }
+
+    #[test]
+    fn test_open_inplace_tamper() {
+        use randombytes::randombytes;
+        for i in (0..32usize) {
+            let k = gen_key();
+            let m = randombytes(i);
+            let n = gen_nonce();
+            let mut c = seal(&m, &n, &k);
+            for j in (0..c.len()) {
+                c[j] ^= 0x20;
+                let c2 = c.clone();
+                assert!(Err(()) == open_inplace(&mut c, &n, &k));
+                assert!(c == c2);
+                c[j] ^= 0x20;
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_short() {
+        let k = gen_key();
+        let n = gen_nonce();
+        for i in (0..MACBYTES) {
+            let c: Vec<u8> = (0..i).map(|x| x as u8).collect();
+            assert!(None == open(&c, &n, &k));
+            let mut c = c;
+            assert!(Err(()) == open_inplace(&mut c, &n, &k));
+        }
+    }
+
     #[test]
     fn test_vector_1() {
         let firstkey = Key([0x1b,0x27,0x55,0x64,0x73,0xe9,0x85,0xd4
@@ -175,8 +458,13 @@ mod test {
                              ,0xe3,0x55,0xa5];
         let c = seal(&m, &nonce, &firstkey);
         assert!(c == c_expected);
+        let (cd, Tag(tag)) = seal_detached(&m, &nonce, &firstkey);
+        assert!(&tag[..] == &c_expected[..MACBYTES]);
+        assert!(&cd[..] == &c_expected[MACBYTES..]);
         let m2 = open(&c, &nonce, &firstkey);
-        assert!(Some(m) == m2);
+        assert!(Some(m.clone()) == m2);
+        let m3 = open_detached(&cd, &Tag(tag), &nonce, &firstkey);
+        assert!(Some(m) == m3);
     }
 
     #[test]
@@ -184,8 +472,10 @@ mod test {
         for _ in (0..256usize) {
             let k = gen_key();
             let n = gen_nonce();
+            let (_, tag) = seal_detached(&[], &n, &k);
             round_trip(k);
             round_trip(n);
+            round_trip(tag);
         }
     }
 }
@@ -213,4 +503,37 @@ mod bench {
             }
         });
     }
+
+    #[bench]
+    fn bench_seal_open_inplace(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            let mut m = Vec::with_capacity(*s + MACBYTES);
+            m.extend(randombytes(*s).into_iter());
+            m
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                seal_inplace(m, &n, &k);
+                open_inplace(m, &n, &k).unwrap();
+                m.drain(..MACBYTES);
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                let (c, tag) = seal_detached(&m, &n, &k);
+                open_detached(&c, &tag, &n, &k).unwrap();
+            }
+        });
+    }
 }
@@ -0,0 +1,45 @@
+//! Authenticated encryption of a sequence of messages
+//!
+//! `secretstream` encrypts a sequence of messages, or a single message split
+//! into an arbitrary number of chunks, using a secret key. Compared to
+//! sealing every chunk with `secretbox::seal()` and a hand-incremented nonce:
+//!
+//! - the messages can't be reordered, removed or duplicated without the
+//!   receiver noticing;
+//! - the end of the stream is marked by a `Tag::Final` message, so a
+//!   truncated stream can be detected;
+//! - no nonce has to be managed by the caller: a random `Header` is
+//!   generated when the stream is created and has to be sent to the
+//!   receiver along with the ciphertexts;
+//! - keys can be rotated (`Tag::Rekey` or `rekey()`) without starting a new
+//!   stream.
+//!
+//! The sending side of a stream is a `Stream<Push>` and the receiving side
+//! is a `Stream<Pull>`.
+//!
+//! # Selected primitive
+//! `crypto::secretstream` is `xchacha20poly1305`, which derives a subkey
+//! from the key and the header and encrypts every message with
+//! ChaCha20-Poly1305 (IETF) using a nonce derived from the header and an
+//! internal counter.
+//!
+//! # Example
+//! ```
+//! use sodiumoxide::crypto::secretstream::{gen_key, Stream, Tag};
+//!
+//! let key = gen_key();
+//! let (mut enc_stream, header) = Stream::init_push(&key).unwrap();
+//! let c1 = enc_stream.push(b"some data", None, Tag::Message).unwrap();
+//! let c2 = enc_stream.finalize(b"some more data", None).unwrap();
+//!
+//! // header is sent along with the ciphertexts
+//! let mut dec_stream = Stream::init_pull(&header, &key).unwrap();
+//! let (m1, tag1) = dec_stream.pull(&c1, None).unwrap();
+//! assert!(&m1[..] == b"some data" && tag1 == Tag::Message);
+//! assert!(!dec_stream.is_finalized());
+//! let (m2, tag2) = dec_stream.pull(&c2, None).unwrap();
+//! assert!(&m2[..] == b"some more data" && tag2 == Tag::Final);
+//! assert!(dec_stream.is_finalized());
+//! ```
+pub use self::xchacha20poly1305::*;
+pub mod xchacha20poly1305;
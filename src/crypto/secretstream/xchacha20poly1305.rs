@@ -0,0 +1,418 @@
+//! `crypto_secretstream_xchacha20poly1305`: a stream of messages encrypted
+//! with ChaCha20-Poly1305 (IETF) under keys and nonces derived with
+//! HChaCha20 from the secret key and a random header.
+use ffi;
+use ffi::{crypto_secretstream_xchacha20poly1305_state,
+          crypto_secretstream_xchacha20poly1305_init_push,
+          crypto_secretstream_xchacha20poly1305_push,
+          crypto_secretstream_xchacha20poly1305_init_pull,
+          crypto_secretstream_xchacha20poly1305_pull,
+          crypto_secretstream_xchacha20poly1305_rekey,
+          crypto_secretstream_xchacha20poly1305_TAG_MESSAGE,
+          crypto_secretstream_xchacha20poly1305_TAG_PUSH,
+          crypto_secretstream_xchacha20poly1305_TAG_REKEY,
+          crypto_secretstream_xchacha20poly1305_TAG_FINAL};
+use libc::{c_ulonglong, size_t};
+use randombytes::randombytes_into;
+use rustc_serialize;
+use std::iter::repeat;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+
+pub const KEYBYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_KEYBYTES;
+pub const HEADERBYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_HEADERBYTES;
+/// Number of bytes every pushed message adds to the length of the plaintext
+pub const ABYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_ABYTES;
+
+/// `Key` for a secret stream
+///
+/// When a `Key` goes out of scope its contents
+/// will be zeroed out
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/// `Header` of a secret stream
+///
+/// The header is generated by `Stream::init_push()` and is required by the
+/// receiver to start decrypting the stream. It doesn't have to be kept
+/// secret.
+#[derive(Copy)]
+pub struct Header(pub [u8; HEADERBYTES]);
+
+newtype_clone!(Header);
+newtype_impl!(Header, HEADERBYTES);
+
+/// `Tag` attached to every message of a secret stream
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Tag {
+    /// The most common tag, that doesn't add any information about the
+    /// nature of the message
+    Message,
+    /// Indicates that the message marks the end of a set of messages, but
+    /// not the end of the stream
+    Push,
+    /// Rotates the key after the message has been processed
+    Rekey,
+    /// Indicates that the message marks the end of the stream, and erases
+    /// the secret key used to encrypt the previous sequence
+    Final,
+}
+
+impl Tag {
+    fn to_u8(self) -> u8 {
+        match self {
+            Tag::Message => crypto_secretstream_xchacha20poly1305_TAG_MESSAGE,
+            Tag::Push => crypto_secretstream_xchacha20poly1305_TAG_PUSH,
+            Tag::Rekey => crypto_secretstream_xchacha20poly1305_TAG_REKEY,
+            Tag::Final => crypto_secretstream_xchacha20poly1305_TAG_FINAL,
+        }
+    }
+
+    fn from_u8(tag: u8) -> Result<Tag, ()> {
+        if tag == crypto_secretstream_xchacha20poly1305_TAG_MESSAGE {
+            Ok(Tag::Message)
+        } else if tag == crypto_secretstream_xchacha20poly1305_TAG_PUSH {
+            Ok(Tag::Push)
+        } else if tag == crypto_secretstream_xchacha20poly1305_TAG_REKEY {
+            Ok(Tag::Rekey)
+        } else if tag == crypto_secretstream_xchacha20poly1305_TAG_FINAL {
+            Ok(Tag::Final)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Marker for the sending side of a `Stream`
+pub enum Push {}
+
+/// Marker for the receiving side of a `Stream`
+pub enum Pull {}
+
+/// `Stream` holds the state of one side of a secret stream
+///
+/// A `Stream<Push>` encrypts messages and is created with
+/// `Stream::init_push()`; a `Stream<Pull>` decrypts them and is created with
+/// `Stream::init_pull()`. Once a message with `Tag::Final` has been pushed or
+/// pulled the stream is finalized and can't be used any more.
+///
+/// When a `Stream` goes out of scope its contents, which include the
+/// current key, will be zeroed out
+pub struct Stream<M> {
+    state: crypto_secretstream_xchacha20poly1305_state,
+    finalized: bool,
+    marker: PhantomData<M>,
+}
+
+impl<M> Drop for Stream<M> {
+    fn drop(&mut self) {
+        unsafe {
+            let sp: *mut crypto_secretstream_xchacha20poly1305_state = &mut self.state;
+            ffi::sodium_memzero(sp as *mut u8, mem::size_of_val(&self.state) as size_t);
+        }
+    }
+}
+
+impl<M> Stream<M> {
+    /// `is_finalized()` returns `true` once a message with `Tag::Final` has
+    /// been pushed to or pulled from the stream
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+
+    /// `rekey()` explicitly rotates the key of the stream
+    ///
+    /// Both sides have to call `rekey()` at the same point in the stream.
+    pub fn rekey(&mut self) {
+        unsafe {
+            crypto_secretstream_xchacha20poly1305_rekey(&mut self.state);
+        }
+    }
+}
+
+impl Stream<Push> {
+    /// `init_push()` creates the sending side of a new stream encrypted with
+    /// the key `k`. It returns the stream and the `Header` that the receiver
+    /// needs to decrypt it.
+    ///
+    /// THREAD SAFETY: `init_push()` is thread-safe provided that you have
+    /// called `sodiumoxide::init()` once before using any other function
+    /// from sodiumoxide.
+    pub fn init_push(&Key(ref k): &Key) -> Result<(Stream<Push>, Header), ()> {
+        let mut header = [0u8; HEADERBYTES];
+        let mut stream = Stream {
+            state: unsafe { mem::zeroed() },
+            finalized: false,
+            marker: PhantomData,
+        };
+        let ret = unsafe {
+            crypto_secretstream_xchacha20poly1305_init_push(&mut stream.state,
+                                                            &mut header,
+                                                            k)
+        };
+        if ret == 0 {
+            Ok((stream, Header(header)))
+        } else {
+            Err(())
+        }
+    }
+
+    /// `push()` encrypts and authenticates the next message `m` of the stream,
+    /// along with the optional additional data `ad` and the tag `tag`.
+    /// It returns a ciphertext that is `ABYTES` longer than `m`.
+    ///
+    /// `push()` returns `Err(())` if the stream is already finalized.
+    pub fn push(&mut self, m: &[u8], ad: Option<&[u8]>, tag: Tag) -> Result<Vec<u8>, ()> {
+        if self.finalized {
+            return Err(());
+        }
+        let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                               .unwrap_or((ptr::null(), 0));
+        let mut c: Vec<u8> = repeat(0u8).take(m.len() + ABYTES).collect();
+        let ret = unsafe {
+            crypto_secretstream_xchacha20poly1305_push(&mut self.state,
+                                                       c.as_mut_ptr(),
+                                                       ptr::null_mut(),
+                                                       m.as_ptr(),
+                                                       m.len() as c_ulonglong,
+                                                       ad_p,
+                                                       ad_len as c_ulonglong,
+                                                       tag.to_u8())
+        };
+        if ret != 0 {
+            return Err(());
+        }
+        if tag == Tag::Final {
+            self.finalized = true;
+        }
+        Ok(c)
+    }
+
+    /// `finalize()` pushes the last message `m` of the stream, tagged with
+    /// `Tag::Final`, and consumes the stream.
+    pub fn finalize(mut self, m: &[u8], ad: Option<&[u8]>) -> Result<Vec<u8>, ()> {
+        self.push(m, ad, Tag::Final)
+    }
+}
+
+impl Stream<Pull> {
+    /// `init_pull()` creates the receiving side of the stream with the header
+    /// `header`, encrypted with the key `k`.
+    pub fn init_pull(&Header(ref header): &Header,
+                     &Key(ref k): &Key) -> Result<Stream<Pull>, ()> {
+        let mut stream = Stream {
+            state: unsafe { mem::zeroed() },
+            finalized: false,
+            marker: PhantomData,
+        };
+        let ret = unsafe {
+            crypto_secretstream_xchacha20poly1305_init_pull(&mut stream.state,
+                                                            header,
+                                                            k)
+        };
+        if ret == 0 {
+            Ok(stream)
+        } else {
+            Err(())
+        }
+    }
+
+    /// `pull()` verifies and decrypts the next ciphertext `c` of the stream,
+    /// along with the optional additional data `ad`. It returns the plaintext
+    /// and the tag it was pushed with.
+    ///
+    /// A `Tag::Final` tag means that the stream is complete: `is_finalized()`
+    /// returns `true` from then on and further calls to `pull()` fail.
+    ///
+    /// If the ciphertext fails verification, is out of order or the stream is
+    /// already finalized, `pull()` returns `Err(())`.
+    pub fn pull(&mut self, c: &[u8], ad: Option<&[u8]>) -> Result<(Vec<u8>, Tag), ()> {
+        if self.finalized || c.len() < ABYTES {
+            return Err(());
+        }
+        let (ad_p, ad_len) = ad.map(|ad| (ad.as_ptr(), ad.len()))
+                               .unwrap_or((ptr::null(), 0));
+        let mut m: Vec<u8> = repeat(0u8).take(c.len() - ABYTES).collect();
+        let mut tag = 0u8;
+        let ret = unsafe {
+            crypto_secretstream_xchacha20poly1305_pull(&mut self.state,
+                                                       m.as_mut_ptr(),
+                                                       ptr::null_mut(),
+                                                       &mut tag,
+                                                       c.as_ptr(),
+                                                       c.len() as c_ulonglong,
+                                                       ad_p,
+                                                       ad_len as c_ulonglong)
+        };
+        if ret != 0 {
+            return Err(());
+        }
+        let tag = try!(Tag::from_u8(tag));
+        if tag == Tag::Final {
+            self.finalized = true;
+        }
+        Ok((m, tag))
+    }
+}
+
+/// `gen_key()` randomly generates a key for a secret stream
+///
+/// THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+/// called `sodiumoxide::init()` once before using any other function
+/// from sodiumoxide.
+pub fn gen_key() -> Key {
+    let mut k = [0; KEYBYTES];
+    randombytes_into(&mut k);
+    Key(k)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::round_trip;
+
+    #[test]
+    fn test_push_pull() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let ms: Vec<Vec<u8>> = (0..64usize).map(|i| randombytes(i * 3)).collect();
+        let mut cs = Vec::new();
+        for m in ms.iter() {
+            cs.push(push.push(m, None, Tag::Message).unwrap());
+        }
+        cs.push(push.finalize(b"", None).unwrap());
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        for (m, c) in ms.iter().zip(cs.iter()) {
+            assert!(c.len() == m.len() + ABYTES);
+            let (m2, tag) = pull.pull(c, None).unwrap();
+            assert!(*m == m2);
+            assert!(tag == Tag::Message);
+            assert!(!pull.is_finalized());
+        }
+        let (m2, tag) = pull.pull(&cs[ms.len()], None).unwrap();
+        assert!(m2.len() == 0);
+        assert!(tag == Tag::Final);
+        assert!(pull.is_finalized());
+    }
+
+    #[test]
+    fn test_tags_and_ad() {
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let tags = [Tag::Message, Tag::Push, Tag::Rekey, Tag::Message, Tag::Final];
+        let cs: Vec<Vec<u8>> = tags.iter().map(|tag| {
+            push.push(b"data", Some(b"ad"), *tag).unwrap()
+        }).collect();
+        assert!(push.is_finalized());
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        for (tag, c) in tags.iter().zip(cs.iter()) {
+            let (m, tag2) = pull.pull(c, Some(b"ad")).unwrap();
+            assert!(&m[..] == b"data");
+            assert!(*tag == tag2);
+        }
+    }
+
+    #[test]
+    fn test_explicit_rekey() {
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let c1 = push.push(b"first", None, Tag::Message).unwrap();
+        push.rekey();
+        let c2 = push.push(b"second", None, Tag::Message).unwrap();
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        pull.pull(&c1, None).unwrap();
+        let mut pull2 = Stream::init_pull(&header, &k).unwrap();
+        pull2.pull(&c1, None).unwrap();
+        // without the matching rekey the next message can't be decrypted
+        assert!(pull2.pull(&c2, None).is_err());
+        pull.rekey();
+        let (m2, _) = pull.pull(&c2, None).unwrap();
+        assert!(&m2[..] == b"second");
+    }
+
+    #[test]
+    fn test_push_after_final() {
+        let k = gen_key();
+        let (mut push, _) = Stream::init_push(&k).unwrap();
+        push.push(b"last", None, Tag::Final).unwrap();
+        assert!(push.push(b"more", None, Tag::Message).is_err());
+    }
+
+    #[test]
+    fn test_pull_after_final() {
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let c1 = push.push(b"last", None, Tag::Final).unwrap();
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        pull.pull(&c1, None).unwrap();
+        assert!(pull.pull(&c1, None).is_err());
+    }
+
+    #[test]
+    fn test_reorder_and_tamper() {
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let c1 = push.push(b"first", None, Tag::Message).unwrap();
+        let mut c2 = push.push(b"second", None, Tag::Message).unwrap();
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        assert!(pull.pull(&c2, None).is_err());
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        pull.pull(&c1, None).unwrap();
+        assert!(pull.pull(&c1, None).is_err());
+
+        for i in (0..c2.len()) {
+            let mut pull = Stream::init_pull(&header, &k).unwrap();
+            pull.pull(&c1, None).unwrap();
+            c2[i] ^= 0x20;
+            assert!(pull.pull(&c2, None).is_err());
+            c2[i] ^= 0x20;
+        }
+
+        let mut pull = Stream::init_pull(&header, &k).unwrap();
+        pull.pull(&c1, None).unwrap();
+        assert!(pull.pull(&c2, Some(b"ad")).is_err());
+    }
+
+    #[test]
+    fn test_wrong_header_or_key() {
+        let k = gen_key();
+        let (mut push, header) = Stream::init_push(&k).unwrap();
+        let c = push.push(b"data", None, Tag::Message).unwrap();
+        let (_, header2) = Stream::init_push(&k).unwrap();
+        let mut pull = Stream::init_pull(&header2, &k).unwrap();
+        assert!(pull.pull(&c, None).is_err());
+        let mut pull = Stream::init_pull(&header, &gen_key()).unwrap();
+        assert!(pull.pull(&c, None).is_err());
+    }
+
+    #[test]
+    fn test_pull_short() {
+        let k = gen_key();
+        let (_, header) = Stream::init_push(&k).unwrap();
+        for i in (0..ABYTES) {
+            let mut pull = Stream::init_pull(&header, &k).unwrap();
+            let c: Vec<u8> = (0..i).map(|x| x as u8).collect();
+            assert!(pull.pull(&c, None).is_err());
+        }
+    }
+
+    #[test]
+    fn test_serialisation() {
+        for _ in (0..256usize) {
+            let k = gen_key();
+            let (_, header) = Stream::init_push(&k).unwrap();
+            round_trip(k);
+            round_trip(header);
+        }
+    }
+}
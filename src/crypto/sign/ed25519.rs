@@ -6,7 +6,9 @@ use ffi;
 use libc::c_ulonglong;
 use std::iter::repeat;
 use std::cmp::{PartialEq, Eq};
+use std::{io, mem};
 use rustc_serialize;
+use crypto::box_;
 pub const SEEDBYTES: usize = ffi::crypto_sign_ed25519_SEEDBYTES;
 pub const SECRETKEYBYTES: usize = ffi::crypto_sign_ed25519_SECRETKEYBYTES;
 pub const PUBLICKEYBYTES: usize = ffi::crypto_sign_ed25519_PUBLICKEYBYTES;
@@ -35,6 +37,47 @@ newtype_drop!(SecretKey);
 newtype_clone!(SecretKey);
 newtype_impl!(SecretKey, SECRETKEYBYTES);
 
+impl SecretKey {
+    /// `seed()` returns the `Seed` the secret key was computed from, so that
+    /// `keypair_from_seed(&sk.seed())` returns the same key pair.
+    ///
+    /// Only the 32-byte seed has to be stored in order to recover the key
+    /// pair.
+    pub fn seed(&self) -> Seed {
+        let &SecretKey(ref sk) = self;
+        let mut seed = [0u8; SEEDBYTES];
+        unsafe {
+            ffi::crypto_sign_ed25519_sk_to_seed(&mut seed, sk);
+        }
+        Seed(seed)
+    }
+
+    /// `public_key()` returns the `PublicKey` corresponding to the secret key.
+    pub fn public_key(&self) -> PublicKey {
+        let &SecretKey(ref sk) = self;
+        let mut pk = [0u8; PUBLICKEYBYTES];
+        unsafe {
+            ffi::crypto_sign_ed25519_sk_to_pk(&mut pk, sk);
+        }
+        PublicKey(pk)
+    }
+
+    /// `to_curve25519()` converts the signature secret key into a
+    /// `box_::SecretKey`, so that the same key pair can be used for both
+    /// signing and public-key authenticated encryption.
+    ///
+    /// The resulting key matches the `box_::PublicKey` returned by
+    /// `PublicKey::to_curve25519()` for the corresponding public key.
+    pub fn to_curve25519(&self) -> box_::SecretKey {
+        let &SecretKey(ref sk) = self;
+        let mut curve25519_sk = [0u8; box_::SECRETKEYBYTES];
+        unsafe {
+            ffi::crypto_sign_ed25519_sk_to_curve25519(&mut curve25519_sk, sk);
+        }
+        box_::SecretKey(curve25519_sk)
+    }
+}
+
 /// `PublicKey` for signatures
 #[derive(Copy)]
 pub struct PublicKey(pub [u8; PUBLICKEYBYTES]);
@@ -42,6 +85,26 @@ pub struct PublicKey(pub [u8; PUBLICKEYBYTES]);
 newtype_clone!(PublicKey);
 newtype_impl!(PublicKey, PUBLICKEYBYTES);
 
+impl PublicKey {
+    /// `to_curve25519()` converts the signature public key into a
+    /// `box_::PublicKey`, so that the same key pair can be used for both
+    /// signing and public-key authenticated encryption.
+    ///
+    /// It returns `Err(())` if the key isn't a valid Ed25519 point, or isn't
+    /// in the main subgroup.
+    pub fn to_curve25519(&self) -> Result<box_::PublicKey, ()> {
+        let &PublicKey(ref pk) = self;
+        let mut curve25519_pk = [0u8; box_::PUBLICKEYBYTES];
+        if unsafe {
+            ffi::crypto_sign_ed25519_pk_to_curve25519(&mut curve25519_pk, pk)
+        } == 0 {
+            Ok(box_::PublicKey(curve25519_pk))
+        } else {
+            Err(())
+        }
+    }
+}
+
 /// Detached signature
 #[derive(Copy)]
 pub struct Signature(pub [u8; SIGNATUREBYTES]);
@@ -146,6 +209,68 @@ pub fn verify_detached(&Signature(ref sig): &Signature,
     }
 }
 
+/// `State` signs or verifies a message fed to it in pieces, using Ed25519ph
+///
+/// Ed25519ph signs the SHA-512 hash of the message. Its signatures are not
+/// compatible with the ones produced by `sign()` and `sign_detached()`, and
+/// have to be verified with a `State` as well.
+#[derive(Clone)]
+pub struct State(ffi::crypto_sign_ed25519ph_state);
+
+impl State {
+    /// `new()` creates a `State` for signing or verifying a new message
+    pub fn new() -> State {
+        unsafe {
+            let mut st: ffi::crypto_sign_ed25519ph_state = mem::zeroed();
+            ffi::crypto_sign_ed25519ph_init(&mut st);
+            State(st)
+        }
+    }
+
+    /// `update()` adds the next piece of the message `m`
+    pub fn update(&mut self, m: &[u8]) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            ffi::crypto_sign_ed25519ph_update(st, m.as_ptr(), m.len() as c_ulonglong);
+        }
+    }
+
+    /// `final_create()` signs the message using the signer's secret key `sk`
+    /// and returns the resulting signature.
+    pub fn final_create(self, &SecretKey(ref sk): &SecretKey) -> Signature {
+        let State(mut st) = self;
+        let mut sig = [0u8; SIGNATUREBYTES];
+        let mut siglen: c_ulonglong = 0;
+        unsafe {
+            ffi::crypto_sign_ed25519ph_final_create(&mut st, &mut sig, &mut siglen, sk);
+        }
+        assert_eq!(siglen, SIGNATUREBYTES as c_ulonglong);
+        Signature(sig)
+    }
+
+    /// `final_verify()` verifies the signature in `sig` against the message
+    /// and the signer's public key `pk`.
+    /// `final_verify()` returns true if the signature is valid, false otherwise.
+    pub fn final_verify(self, &Signature(ref sig): &Signature,
+                        &PublicKey(ref pk): &PublicKey) -> bool {
+        let State(mut st) = self;
+        unsafe {
+            0 == ffi::crypto_sign_ed25519ph_final_verify(&mut st, sig, pk)
+        }
+    }
+}
+
+impl io::Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -302,6 +427,188 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_state_vector() {
+        // RFC 8032, section 7.3 (Ed25519ph)
+        let seed = Seed([0x83, 0x3f, 0xe6, 0x24, 0x09, 0x23, 0x7b, 0x9d,
+                         0x62, 0xec, 0x77, 0x58, 0x75, 0x20, 0x91, 0x1e,
+                         0x9a, 0x75, 0x9c, 0xec, 0x1d, 0x19, 0x75, 0x5b,
+                         0x7d, 0xa9, 0x01, 0xb9, 0x6d, 0xca, 0x3d, 0x42]);
+        let pk_expected = [0xec, 0x17, 0x2b, 0x93, 0xad, 0x5e, 0x56, 0x3b,
+                           0xf4, 0x93, 0x2c, 0x70, 0xe1, 0x24, 0x50, 0x34,
+                           0xc3, 0x54, 0x67, 0xef, 0x2e, 0xfd, 0x4d, 0x64,
+                           0xeb, 0xf8, 0x19, 0x68, 0x34, 0x67, 0xe2, 0xbf];
+        let sig_expected = [0x98, 0xa7, 0x02, 0x22, 0xf0, 0xb8, 0x12, 0x1a,
+                            0xa9, 0xd3, 0x0f, 0x81, 0x3d, 0x68, 0x3f, 0x80,
+                            0x9e, 0x46, 0x2b, 0x46, 0x9c, 0x7f, 0xf8, 0x76,
+                            0x39, 0x49, 0x9b, 0xb9, 0x4e, 0x6d, 0xae, 0x41,
+                            0x31, 0xf8, 0x50, 0x42, 0x46, 0x3c, 0x2a, 0x35,
+                            0x5a, 0x20, 0x03, 0xd0, 0x62, 0xad, 0xf5, 0xaa,
+                            0xa1, 0x0b, 0x8c, 0x61, 0xe6, 0x36, 0x06, 0x2a,
+                            0xaa, 0xd1, 0x1c, 0x2a, 0x26, 0x08, 0x34, 0x06];
+        let (pk, sk) = keypair_from_seed(&seed);
+        assert!(pk[..] == pk_expected[..]);
+        let mut state = State::new();
+        state.update(b"abc");
+        let sig = state.final_create(&sk);
+        assert!(sig[..] == sig_expected[..]);
+        let mut state = State::new();
+        state.update(b"a");
+        state.update(b"bc");
+        assert!(state.final_verify(&sig, &pk));
+    }
+
+    #[test]
+    fn test_state_vectors() {
+        // keys and messages from the test vectors of the Python
+        // implementation, split at random chunk boundaries
+        use rustc_serialize::hex::FromHex;
+        use randombytes::randombytes;
+        use std::fs::File;
+        use std::io::{BufRead, BufReader};
+
+        let r = BufReader::new(File::open("testvectors/ed25519.input").unwrap());
+        for mline in r.lines() {
+            let line = mline.unwrap();
+            let mut x = line.split(':');
+            let x0 = x.next().unwrap();
+            let _ = x.next().unwrap();
+            let x2 = x.next().unwrap();
+            let seed_bytes = x0[..64].from_hex().unwrap();
+            let seed = Seed::from_slice(&seed_bytes).unwrap();
+            let (pk, sk) = keypair_from_seed(&seed);
+            let m = x2.from_hex().unwrap();
+
+            let mut state = State::new();
+            state.update(&m);
+            let sig = state.final_create(&sk);
+
+            let mut state = State::new();
+            let mut rest = &m[..];
+            while rest.len() > 0 {
+                let n = randombytes(1)[0] as usize % rest.len() + 1;
+                state.update(&rest[..n]);
+                rest = &rest[n..];
+            }
+            let state2 = state.clone();
+            assert!(state.final_create(&sk) == sig);
+            assert!(state2.clone().final_verify(&sig, &pk));
+            // Ed25519ph signatures are not Ed25519 signatures
+            assert!(!verify_detached(&sig, &m, &pk));
+            assert!(sign_detached(&m, &sk) != sig);
+            let mut sig_tampered = sig;
+            sig_tampered.0[0] ^= 0x20;
+            assert!(!state2.final_verify(&sig_tampered, &pk));
+        }
+    }
+
+    #[test]
+    fn test_state_tamper() {
+        use randombytes::randombytes;
+        for i in (1..64usize) {
+            let (pk, sk) = gen_keypair();
+            let mut m = randombytes(i);
+            let mut state = State::new();
+            state.update(&m);
+            let sig = state.final_create(&sk);
+            for j in (0..m.len()) {
+                m[j] ^= 0x20;
+                let mut state = State::new();
+                state.update(&m);
+                assert!(!state.final_verify(&sig, &pk));
+                m[j] ^= 0x20;
+            }
+            let (pk2, _) = gen_keypair();
+            let mut state = State::new();
+            state.update(&m);
+            assert!(!state.final_verify(&sig, &pk2));
+        }
+    }
+
+    #[test]
+    fn test_state_io_copy() {
+        use randombytes::randombytes;
+        use std::io;
+        let (pk, sk) = gen_keypair();
+        let m = randombytes(100000);
+        let mut state = State::new();
+        io::copy(&mut &m[..], &mut state).unwrap();
+        let sig = state.final_create(&sk);
+        let mut state = State::new();
+        state.update(&m);
+        assert!(state.final_verify(&sig, &pk));
+    }
+
+    #[test]
+    fn test_seed_public_key() {
+        use randombytes::randombytes_into;
+        for _ in (0..64usize) {
+            let mut seedbuf = [0; SEEDBYTES];
+            randombytes_into(&mut seedbuf);
+            let seed = Seed(seedbuf);
+            let (pk, sk) = keypair_from_seed(&seed);
+            assert!(sk.seed() == seed);
+            assert!(sk.public_key() == pk);
+            let (pk2, sk2) = keypair_from_seed(&sk.seed());
+            assert!(pk2 == pk);
+            assert!(sk2 == sk);
+        }
+    }
+
+    #[test]
+    fn test_public_key_gen_keypair() {
+        let (pk, sk) = gen_keypair();
+        assert!(sk.public_key() == pk);
+    }
+
+    #[test]
+    fn test_to_curve25519_vector() {
+        // corresponding to test/default/ed25519_convert.c in libsodium
+        let seed = Seed([0x42, 0x11, 0x51, 0xa4, 0x59, 0xfa, 0xea, 0xde,
+                         0x3d, 0x24, 0x71, 0x15, 0xf9, 0x4a, 0xed, 0xae,
+                         0x42, 0x31, 0x81, 0x24, 0x09, 0x5a, 0xfa, 0xbe,
+                         0x4d, 0x14, 0x51, 0xa5, 0x59, 0xfa, 0xed, 0xee]);
+        let (pk, sk) = keypair_from_seed(&seed);
+        let box_::PublicKey(curve25519_pk) = pk.to_curve25519().unwrap();
+        let box_::SecretKey(curve25519_sk) = sk.to_curve25519();
+        assert!(curve25519_pk == [0xf1, 0x81, 0x4f, 0x0e, 0x8f, 0xf1, 0x04, 0x3d,
+                                  0x8a, 0x44, 0xd2, 0x5b, 0xab, 0xff, 0x3c, 0xed,
+                                  0xca, 0xe6, 0xc2, 0x2c, 0x3e, 0xda, 0xa4, 0x8f,
+                                  0x85, 0x7a, 0xe7, 0x0d, 0xe2, 0xba, 0xae, 0x50]);
+        assert!(curve25519_sk == [0x80, 0x52, 0x03, 0x03, 0x76, 0xd4, 0x71, 0x12,
+                                  0xbe, 0x7f, 0x73, 0xed, 0x7a, 0x01, 0x92, 0x93,
+                                  0xdd, 0x12, 0xad, 0x91, 0x0b, 0x65, 0x44, 0x55,
+                                  0x79, 0x8b, 0x46, 0x67, 0xd7, 0x3d, 0xe1, 0x66]);
+    }
+
+    #[test]
+    fn test_to_curve25519_keypair() {
+        use crypto::scalarmult::curve25519::{scalarmult_base, Scalar};
+        use randombytes::randombytes;
+        for i in (0..64usize) {
+            let (pk, sk) = gen_keypair();
+            let box_pk = pk.to_curve25519().unwrap();
+            let box_sk = sk.to_curve25519();
+            let box_::SecretKey(ref s) = box_sk;
+            assert!(scalarmult_base(&Scalar(*s)).0 == box_pk.0);
+            let m = randombytes(i);
+            let n = box_::gen_nonce();
+            let c = box_::seal(&m, &n, &box_pk, &box_sk);
+            assert!(Some(m) == box_::open(&c, &n, &box_pk, &box_sk));
+        }
+    }
+
+    #[test]
+    fn test_to_curve25519_invalid() {
+        // corresponding to test/default/ed25519_convert.c in libsodium
+        let mut pk = PublicKey([0; PUBLICKEYBYTES]);
+        assert!(pk.to_curve25519().is_err());
+        pk.0[0] = 0x02;
+        assert!(pk.to_curve25519().is_err());
+        pk.0[0] = 0x05;
+        assert!(pk.to_curve25519().is_err());
+    }
+
     #[test]
     fn test_serialisation() {
         use randombytes::randombytes;
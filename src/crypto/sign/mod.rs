@@ -41,6 +41,37 @@
 //! let signature = sign::sign_detached(data_to_sign, &sk);
 //! assert!(sign::verify_detached(&signature, data_to_sign, &pk));
 //! ```
+//!
+//! # Example (streaming Ed25519ph signatures)
+//! ```
+//! use std::io;
+//! use sodiumoxide::crypto::sign;
+//! let (pk, sk) = sign::gen_keypair();
+//! let mut artifact: &[u8] = &[0; 100000]; // e.g. a `File`
+//! let mut state = sign::State::new();
+//! io::copy(&mut artifact, &mut state).unwrap();
+//! let signature = state.final_create(&sk);
+//!
+//! let mut state = sign::State::new();
+//! state.update(&[0; 50000]);
+//! state.update(&[0; 50000]);
+//! assert!(state.final_verify(&signature, &pk));
+//! ```
+//!
+//! # Example (using signing keys for encryption)
+//! ```
+//! use sodiumoxide::crypto::{box_, sign};
+//! let (our_pk, our_sk) = sign::gen_keypair();
+//! let (their_pk, their_sk) = sign::gen_keypair();
+//! let our_box_sk = our_sk.to_curve25519();
+//! let their_box_pk = their_pk.to_curve25519().unwrap();
+//! let nonce = box_::gen_nonce();
+//! let ciphertext = box_::seal(b"some data", &nonce, &their_box_pk, &our_box_sk);
+//! let their_plaintext = box_::open(&ciphertext, &nonce,
+//!                                  &our_pk.to_curve25519().unwrap(),
+//!                                  &their_sk.to_curve25519()).unwrap();
+//! assert!(b"some data" == &their_plaintext[..]);
+//! ```
 pub use self::ed25519::*;
 pub mod ed25519;
 pub mod edwards25519sha512batch;
@@ -1,6 +1,7 @@
 //! `crypto_stream_chacha20` (Chacha20)
 use ffi::{crypto_stream_chacha20,
           crypto_stream_chacha20_xor,
+          crypto_stream_chacha20_xor_ic,
           crypto_stream_chacha20_KEYBYTES,
           crypto_stream_chacha20_NONCEBYTES};
 
@@ -9,6 +10,8 @@ stream_module!(crypto_stream_chacha20,
                crypto_stream_chacha20_KEYBYTES,
                crypto_stream_chacha20_NONCEBYTES);
 
+stream_xor_ic_impl!(crypto_stream_chacha20_xor_ic, u64);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,70 @@
+//! `crypto_stream_chacha20_ietf` (Chacha20 as specified in
+//! [RFC 8439](https://tools.ietf.org/html/rfc8439)), with a 96-bit nonce and a
+//! 32-bit block counter
+use ffi::{crypto_stream_chacha20_ietf,
+          crypto_stream_chacha20_ietf_xor,
+          crypto_stream_chacha20_ietf_xor_ic,
+          crypto_stream_chacha20_ietf_KEYBYTES,
+          crypto_stream_chacha20_ietf_NONCEBYTES};
+
+stream_module!(crypto_stream_chacha20_ietf,
+               crypto_stream_chacha20_ietf_xor,
+               crypto_stream_chacha20_ietf_KEYBYTES,
+               crypto_stream_chacha20_ietf_NONCEBYTES);
+
+stream_xor_ic_impl!(crypto_stream_chacha20_ietf_xor_ic, u32, max_blocks = 1 << 32);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_1() {
+        // test vector from https://tools.ietf.org/html/rfc8439#section-2.4.2
+        let key = Key([0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                       0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+                       0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+                       0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f]);
+        let nonce = Nonce([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4a,
+                           0x00, 0x00, 0x00, 0x00]);
+        let m = b"Ladies and Gentlemen of the class of '99: If I could offer you \
+                  only one tip for the future, sunscreen would be it.";
+        let expected = [0x6e, 0x2e, 0x35, 0x9a, 0x25, 0x68, 0xf9, 0x80,
+                        0x41, 0xba, 0x07, 0x28, 0xdd, 0x0d, 0x69, 0x81,
+                        0xe9, 0x7e, 0x7a, 0xec, 0x1d, 0x43, 0x60, 0xc2,
+                        0x0a, 0x27, 0xaf, 0xcc, 0xfd, 0x9f, 0xae, 0x0b,
+                        0xf9, 0x1b, 0x65, 0xc5, 0x52, 0x47, 0x33, 0xab,
+                        0x8f, 0x59, 0x3d, 0xab, 0xcd, 0x62, 0xb3, 0x57,
+                        0x16, 0x39, 0xd6, 0x24, 0xe6, 0x51, 0x52, 0xab,
+                        0x8f, 0x53, 0x0c, 0x35, 0x9f, 0x08, 0x61, 0xd8,
+                        0x07, 0xca, 0x0d, 0xbf, 0x50, 0x0d, 0x6a, 0x61,
+                        0x56, 0xa3, 0x8e, 0x08, 0x8a, 0x22, 0xb6, 0x5e,
+                        0x52, 0xbc, 0x51, 0x4d, 0x16, 0xcc, 0xf8, 0x06,
+                        0x81, 0x8c, 0xe9, 0x1a, 0xb7, 0x79, 0x37, 0x36,
+                        0x5a, 0xf9, 0x0b, 0xbf, 0x74, 0xa3, 0x5b, 0xe6,
+                        0xb4, 0x0b, 0x8e, 0xed, 0xf2, 0x78, 0x5e, 0x42,
+                        0x87, 0x4d];
+        // the RFC starts encrypting with the block counter set to 1
+        let c = stream_xor_ic(m, &nonce, 1, &key).unwrap();
+        assert!(&c[..] == &expected[..]);
+        let s = stream(64 + m.len(), &nonce, &key);
+        let c2: Vec<u8> = m.iter().zip(s[64..].iter()).map(|(a, b)| a ^ b).collect();
+        assert!(&c2[..] == &expected[..]);
+    }
+
+    #[test]
+    fn test_stream_xor_ic_counter_overflow() {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ic = 0xffffffff;
+        assert!(stream_xor_ic(&[0u8; 0], &n, ic, &k).is_ok());
+        assert!(stream_xor_ic(&[0u8; 64], &n, ic, &k).is_ok());
+        assert!(stream_xor_ic(&[0u8; 65], &n, ic, &k).is_err());
+        assert!(stream_xor_ic(&[0u8; 128], &n, ic, &k).is_err());
+        assert!(stream_xor_ic(&[0u8; 128], &n, ic - 1, &k).is_ok());
+        let mut m = [0u8; 128];
+        assert!(stream_xor_ic_inplace(&mut m, &n, ic, &k).is_err());
+        assert!(&m[..] == &[0u8; 128][..]);
+        assert!(stream_xor_ic_inplace(&mut m, &n, ic - 1, &k).is_ok());
+    }
+}
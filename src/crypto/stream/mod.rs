@@ -34,15 +34,20 @@
 //! # Alternate primitives
 //! NaCl supports the following secret-key encryption functions:
 //!
-//! ------------------------------------------------------------
-//! |crypto_stream           |primitive   |KEYBYTES |NONCEBYTES|
-//! |------------------------|------------|---------|----------|
-//! |crypto_stream_aes128ctr |AES-128-CTR |16       |16        |
-//! |crypto_stream_salsa208  |Salsa20/8   |32       |8         |
-//! |crypto_stream_salsa2012 |Salsa20/12  |32       |8         |
-//! |crypto_stream_salsa20   |Salsa20/20  |32       |8         |
-//! |crypto_stream_xsalsa20  |XSalsa20/20 |32       |24        |
-//! ------------------------------------------------------------
+//! ----------------------------------------------------------------
+//! |crypto_stream               |primitive   |KEYBYTES |NONCEBYTES|
+//! |----------------------------|------------|---------|----------|
+//! |crypto_stream_chacha20      |Chacha20/20 |32       |8         |
+//! |crypto_stream_chacha20_ietf |Chacha20/20 |32       |12        |
+//! |crypto_stream_salsa208      |Salsa20/8   |32       |8         |
+//! |crypto_stream_salsa2012     |Salsa20/12  |32       |8         |
+//! |crypto_stream_salsa20       |Salsa20/20  |32       |8         |
+//! |crypto_stream_xchacha20     |XChacha20/20|32       |24        |
+//! |crypto_stream_xsalsa20      |XSalsa20/20 |32       |24        |
+//! ----------------------------------------------------------------
+//!
+//! `crypto_stream_aes128ctr` was removed in sodiumoxide 0.0.6, since libsodium
+//! 1.0.15 no longer provides it.
 //!
 //! Beware that several of these primitives have 8-byte nonces. For those
 //! primitives it is no longer true that randomly generated nonces have negligible
@@ -50,6 +55,14 @@
 //! on using these primitives, are advised to use a randomly derived key for each
 //! message.
 //!
+//! All of these primitives except `salsa208` and `salsa2012` also provide
+//! `stream_xor_ic()`, which starts from a given 64-byte block of the stream, so
+//! that part of a large ciphertext can be decrypted without processing what
+//! comes before it.
+//! Since the block counter of `chacha20_ietf` is only 32 bits long, its
+//! `stream_xor_ic()` returns `Err(())` instead of running past the end of the
+//! stream.
+//!
 //! # Example (keystream generation)
 //! ```
 //! use sodiumoxide::crypto::stream;
@@ -84,12 +97,26 @@
 //! stream::stream_xor_inplace(plaintext, &nonce, &key);
 //! assert_eq!(plaintext, &mut [0, 1, 2, 3]);
 //! ```
+//!
+//! # Example (seeking)
+//! ```
+//! use sodiumoxide::crypto::stream;
+//!
+//! let key = stream::gen_key();
+//! let nonce = stream::gen_nonce();
+//! let plaintext = &[7u8; 256][..];
+//! let ciphertext = stream::stream_xor(plaintext, &nonce, &key);
+//! // decrypt the last 128 bytes only, starting at block 128 / 64 = 2
+//! let tail = stream::stream_xor_ic(&ciphertext[128..], &nonce, 2, &key);
+//! assert_eq!(&plaintext[128..], &tail[..]);
+//! ```
 pub use self::xsalsa20::*;
 #[macro_use]
 mod stream_macros;
 pub mod xsalsa20;
-pub mod aes128ctr;
 pub mod salsa208;
 pub mod salsa2012;
 pub mod salsa20;
 pub mod chacha20;
+pub mod chacha20_ietf;
+pub mod xchacha20;
@@ -3,6 +3,7 @@
 //! cipher is conjectured to meet the standard notion of unpredictability.
 use ffi::{crypto_stream_salsa20,
           crypto_stream_salsa20_xor,
+          crypto_stream_salsa20_xor_ic,
           crypto_stream_salsa20_KEYBYTES,
           crypto_stream_salsa20_NONCEBYTES};
 
@@ -11,6 +12,8 @@ stream_module!(crypto_stream_salsa20,
                crypto_stream_salsa20_KEYBYTES,
                crypto_stream_salsa20_NONCEBYTES);
 
+stream_xor_ic_impl!(crypto_stream_salsa20_xor_ic, u64);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -191,3 +191,109 @@ mod bench_m {
 }
 
 ));
+
+macro_rules! stream_xor_ic_impl (
+($xor_ic_name:ident, $ic:ty) => (
+stream_xor_ic_impl!(@impl $xor_ic_name, $ic, Vec<u8>, (), [], []);
+);
+($xor_ic_name:ident, $ic:ty, max_blocks = $max_blocks:expr) => (
+stream_xor_ic_impl!(@impl $xor_ic_name, $ic, Result<Vec<u8>, ()>, Result<(), ()>,
+                    [check_ic], [.unwrap()]
+                    #[doc = ""]
+                    #[doc = "If `m` needs more blocks than are left after block `ic`, the"]
+                    #[doc = "message isn't encrypted and `Err(())` is returned."]);
+
+fn check_ic(mlen: usize, ic: $ic) -> Result<(), ()> {
+    // libsodium aborts if the block counter would wrap around
+    if (mlen as u64 + 63) / 64 > $max_blocks - ic as u64 {
+        return Err(());
+    }
+    Ok(())
+}
+);
+(@wrap [], $e:expr) => ($e);
+(@wrap [$check:ident], $e:expr) => (Ok($e));
+(@impl $xor_ic_name:ident, $ic:ty, $ret:ty, $ret_inplace:ty,
+       [$($check:ident)*], [$($unwrap:tt)*] $(#[$doc:meta])*) => (
+
+/// `stream_xor_ic()` encrypts a message `m` using a secret key `k` and a nonce `n`,
+/// starting from block `ic` of the output of `stream()` instead of the first block.
+/// The `stream_xor_ic()` function returns the ciphertext `c`.
+///
+/// Each block of the stream is 64 bytes long, so `stream_xor_ic()` can be used to
+/// encrypt or decrypt part of a larger message starting at a multiple of 64 bytes,
+/// without processing what comes before.
+$(#[$doc])*
+pub fn stream_xor_ic(m: &[u8],
+                     &Nonce(ref n): &Nonce,
+                     ic: $ic,
+                     &Key(ref k): &Key) -> $ret {
+    $(try!($check(m.len(), ic));)*
+    unsafe {
+        let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+        $xor_ic_name(c.as_mut_ptr(),
+                     m.as_ptr(),
+                     m.len() as c_ulonglong,
+                     n,
+                     ic,
+                     k);
+        stream_xor_ic_impl!(@wrap [$($check)*], c)
+    }
+}
+
+/// `stream_xor_ic_inplace()` encrypts a message `m` using a secret key `k` and a
+/// nonce `n`, starting from block `ic` of the output of `stream()`.
+/// The `stream_xor_ic_inplace()` function encrypts the message in place.
+$(#[$doc])*
+pub fn stream_xor_ic_inplace(m: &mut [u8],
+                             &Nonce(ref n): &Nonce,
+                             ic: $ic,
+                             &Key(ref k): &Key) -> $ret_inplace {
+    $(try!($check(m.len(), ic));)*
+    unsafe {
+        $xor_ic_name(m.as_mut_ptr(),
+                     m.as_ptr(),
+                     m.len() as c_ulonglong,
+                     n,
+                     ic,
+                     k);
+    }
+    stream_xor_ic_impl!(@wrap [$($check)*], ())
+}
+
+#[cfg(test)]
+mod test_ic {
+    use super::*;
+
+    #[test]
+    fn test_stream_xor_ic_zero() {
+        use randombytes::randombytes;
+        for i in (0..1024usize) {
+            let k = gen_key();
+            let n = gen_nonce();
+            let m = randombytes(i);
+            let c = stream_xor(&m, &n, &k);
+            let c2 = stream_xor_ic(&m, &n, 0, &k)$($unwrap)*;
+            assert!(c == c2);
+        }
+    }
+
+    #[test]
+    fn test_stream_xor_ic_seek() {
+        use randombytes::randombytes;
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(1024);
+        let c = stream_xor(&m, &n, &k);
+        for block in (0..16usize) {
+            let c2 = stream_xor_ic(&m[block * 64..], &n, block as $ic, &k)$($unwrap)*;
+            assert!(&c[block * 64..] == &c2[..]);
+            let mut m2 = c[block * 64..].to_vec();
+            stream_xor_ic_inplace(&mut m2, &n, block as $ic, &k)$($unwrap)*;
+            assert!(&m[block * 64..] == &m2[..]);
+        }
+    }
+}
+
+);
+);
@@ -0,0 +1,43 @@
+//! `crypto_stream_xchacha20` (XChacha20), a variant of Chacha20 with a 192-bit
+//! nonce that is long enough to be randomly generated
+use ffi::{crypto_stream_xchacha20,
+          crypto_stream_xchacha20_xor,
+          crypto_stream_xchacha20_xor_ic,
+          crypto_stream_xchacha20_KEYBYTES,
+          crypto_stream_xchacha20_NONCEBYTES};
+
+stream_module!(crypto_stream_xchacha20,
+               crypto_stream_xchacha20_xor,
+               crypto_stream_xchacha20_KEYBYTES,
+               crypto_stream_xchacha20_NONCEBYTES);
+
+stream_xor_ic_impl!(crypto_stream_xchacha20_xor_ic, u64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_1() {
+        // test vector taken from libsodium: test/default/xchacha20.c
+        let key = Key([0x5f, 0x57, 0x63, 0xff, 0x9a, 0x30, 0xc9, 0x5d,
+                       0xa5, 0xc9, 0xf2, 0xa8, 0xdf, 0xd7, 0xcc, 0x6e,
+                       0xfd, 0x9d, 0xfb, 0x43, 0x18, 0x12, 0xc0, 0x75,
+                       0xaa, 0x3e, 0x4f, 0x32, 0xe0, 0x4f, 0x53, 0xe4]);
+        let nonce = Nonce([0xa5, 0xfa, 0x89, 0x0e, 0xfa, 0x3b, 0x9a, 0x03,
+                           0x4d, 0x37, 0x79, 0x26, 0xce, 0x0e, 0x08, 0xee,
+                           0x6d, 0x7f, 0xac, 0xca, 0xee, 0x41, 0xb7, 0x71]);
+        let expected = [0x8a, 0x1a, 0x5b, 0xa8, 0x98, 0xbd, 0xbc, 0xff,
+                        0x60, 0x2b, 0x10, 0x36, 0xe4, 0x69, 0xa1, 0x8a,
+                        0x5e, 0x45, 0x78, 0x9d, 0x0e, 0x8d, 0x98, 0x37,
+                        0xd8, 0x1a, 0x23, 0x88, 0xa5, 0x2b, 0x0b, 0x6a,
+                        0x0f, 0x51, 0x89, 0x15, 0x28, 0xf4, 0x24, 0xc4,
+                        0xa7, 0xf4, 0x92, 0xa8, 0xdd, 0x7b, 0xce, 0x8b,
+                        0xac, 0x19, 0xfb, 0xdb, 0xe1, 0xfb, 0x37, 0x9a,
+                        0xc0];
+        let output = stream(expected.len(), &nonce, &key);
+        assert!(output[..] == expected[..]);
+        let output2 = stream_xor_ic(&expected, &nonce, 0, &key);
+        assert!(output2.iter().all(|&b| b == 0));
+    }
+}
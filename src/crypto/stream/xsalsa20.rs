@@ -4,6 +4,7 @@
 //! unpredictability.
 use ffi::{crypto_stream_xsalsa20,
           crypto_stream_xsalsa20_xor,
+          crypto_stream_xsalsa20_xor_ic,
           crypto_stream_xsalsa20_KEYBYTES,
           crypto_stream_xsalsa20_NONCEBYTES};
 
@@ -12,6 +13,8 @@ stream_module!(crypto_stream_xsalsa20,
                crypto_stream_xsalsa20_KEYBYTES,
                crypto_stream_xsalsa20_NONCEBYTES);
 
+stream_xor_ic_impl!(crypto_stream_xsalsa20_xor_ic, u64);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -28,9 +28,15 @@
 //!
 //!  `crypto::sign`
 //!
+//!  `crypto::kx`
+//!
 //! # Secret-key cryptography
 //!  `crypto::secretbox`
 //!
+//!  `crypto::aead`
+//!
+//!  `crypto::secretstream`
+//!
 //!  `crypto::stream`
 //!
 //!  `crypto::auth`
@@ -40,6 +46,12 @@
 //! # Low-level functions
 //!  `crypto::hash`
 //!
+//!  `crypto::generichash`
+//!
+//!  `crypto::kdf`
+//!
+//!  `crypto::hkdf`
+//!
 //!  `crypto::verify`
 //!
 //!  `crypto::shorthash`
@@ -49,6 +61,7 @@
 #![warn(non_upper_case_globals)]
 #![warn(non_camel_case_types)]
 #![warn(unused_qualifications)]
+#![cfg_attr(feature = "benchmarks", feature(test))]
 
 extern crate libsodium_sys as ffi;
 extern crate libc;
@@ -64,7 +77,6 @@ pub fn init() -> bool {
     }
 }
 
-mod marshal;
 #[macro_use]
 mod newtype_macros;
 
@@ -78,9 +90,15 @@ pub mod crypto {
     pub mod box_;
     pub mod sign;
     pub mod scalarmult;
+    pub mod kx;
     pub mod auth;
     pub mod hash;
+    pub mod generichash;
+    pub mod kdf;
+    pub mod hkdf;
     pub mod secretbox;
+    pub mod aead;
+    pub mod secretstream;
     pub mod onetimeauth;
     pub mod pwhash;
     pub mod stream;
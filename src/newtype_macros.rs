@@ -134,3 +134,125 @@ macro_rules! newtype_impl (($newtype:ident, $len:expr) => (
         }
     }
     ));
+
+/// Counterpart of the indexing part of `newtype_impl!` for types of variable
+/// length: `$this => $bytes` gives the bytes of an object `$this`
+macro_rules! newtype_varlen_index (($newtype:ident, $this:ident => $bytes:expr) => (
+    /// Allows a user to access the byte contents of an object as a slice.
+    ///
+    /// WARNING: it might be tempting to do comparisons on objects
+    /// by using `x[a..b] == y[a..b]`. This will open up for timing attacks
+    /// when comparing for example authenticator tags. Because of this only
+    /// use the comparison functions exposed by the sodiumoxide API.
+    impl ::std::ops::Index<::std::ops::Range<usize>> for $newtype {
+        type Output = [u8];
+        fn index(&self, _index: ::std::ops::Range<usize>) -> &[u8] {
+            let $this = self;
+            $bytes.index(_index)
+        }
+    }
+    /// Allows a user to access the byte contents of an object as a slice.
+    ///
+    /// WARNING: it might be tempting to do comparisons on objects
+    /// by using `x[..b] == y[..b]`. This will open up for timing attacks
+    /// when comparing for example authenticator tags. Because of this only
+    /// use the comparison functions exposed by the sodiumoxide API.
+    impl ::std::ops::Index<::std::ops::RangeTo<usize>> for $newtype {
+        type Output = [u8];
+        fn index(&self, _index: ::std::ops::RangeTo<usize>) -> &[u8] {
+            let $this = self;
+            $bytes.index(_index)
+        }
+    }
+    /// Allows a user to access the byte contents of an object as a slice.
+    ///
+    /// WARNING: it might be tempting to do comparisons on objects
+    /// by using `x[a..] == y[a..]`. This will open up for timing attacks
+    /// when comparing for example authenticator tags. Because of this only
+    /// use the comparison functions exposed by the sodiumoxide API.
+    impl ::std::ops::Index<::std::ops::RangeFrom<usize>> for $newtype {
+        type Output = [u8];
+        fn index(&self, _index: ::std::ops::RangeFrom<usize>) -> &[u8] {
+            let $this = self;
+            $bytes.index(_index)
+        }
+    }
+    /// Allows a user to access the byte contents of an object as a slice.
+    ///
+    /// WARNING: it might be tempting to do comparisons on objects
+    /// by using `x[] == y[]`. This will open up for timing attacks
+    /// when comparing for example authenticator tags. Because of this only
+    /// use the comparison functions exposed by the sodiumoxide API.
+    impl ::std::ops::Index<::std::ops::RangeFull> for $newtype {
+        type Output = [u8];
+        fn index(&self, _index: ::std::ops::RangeFull) -> &[u8] {
+            let $this = self;
+            $bytes.index(_index)
+        }
+    }
+    ));
+
+/// `as_str()`, `info()` and `FromStr` for the `HashedPassword` of a password
+/// hashing primitive whose strings are produced by `$algorithm`
+macro_rules! hashed_password_str_impl (($strbytes:expr, $algorithm:expr) => (
+    impl HashedPassword {
+        /// `as_str()` returns the password verification string without its zero
+        /// terminator
+        ///
+        /// If the bytes before the terminator aren't valid UTF-8, which can only
+        /// happen if the `HashedPassword` wasn't created by this module, only the
+        /// valid part is returned.
+        pub fn as_str(&self) -> &str {
+            use std::str;
+            let HashedPassword(ref bs) = *self;
+            let len = bs.iter().position(|&b| b == 0).unwrap_or($strbytes);
+            match str::from_utf8(&bs[..len]) {
+                Ok(s) => s,
+                Err(e) => str::from_utf8(&bs[..e.valid_up_to()]).unwrap(),
+            }
+        }
+
+        /// `info()` parses the password verification string and returns the
+        /// algorithm, cost parameters and salt it contains
+        ///
+        /// It returns a `ParseError` if the string is malformed or was produced
+        /// by another primitive.
+        pub fn info(&self)
+                -> Result<::crypto::pwhash::HashInfo, ::crypto::pwhash::ParseError> {
+            use crypto::pwhash::{parse, ParseError};
+            let info = try!(parse(self.as_str()));
+            if info.algorithm != $algorithm {
+                return Err(ParseError::WrongAlgorithm);
+            }
+            Ok(info)
+        }
+    }
+    impl ::std::str::FromStr for HashedPassword {
+        type Err = ::crypto::pwhash::ParseError;
+
+        /// `from_str()` creates a `HashedPassword` from a password verification
+        /// string, e.g. one loaded from a database, with or without its zero
+        /// terminator
+        ///
+        /// It returns a `ParseError` if the string is too long, malformed, or was
+        /// produced by another primitive.
+        fn from_str(s: &str) -> Result<HashedPassword, ::crypto::pwhash::ParseError> {
+            use crypto::pwhash::ParseError;
+            use crypto::pwhash::parse::check;
+            let s = match s.find('\0') {
+                Some(i) if s[i..].bytes().all(|b| b == 0) => &s[..i],
+                Some(_) => return Err(ParseError::Malformed),
+                None => s,
+            };
+            try!(check(s, $strbytes, $algorithm));
+            let mut pwh = HashedPassword([0; $strbytes]);
+            {
+                let HashedPassword(ref mut bs) = pwh;
+                for (b, &sb) in bs.iter_mut().zip(s.as_bytes().iter()) {
+                    *b = sb;
+                }
+            }
+            Ok(pwh)
+        }
+    }
+    ));